@@ -33,7 +33,7 @@ impl Affine {
         Self::new(alphabet, 1, shift)
     }
     pub fn new_rot13() -> Result<Self, String> {
-        Self::new(&ENGLISH, 1, 13)
+        Self::new(ENGLISH, 1, 13)
     }
 
     fn substitute(&self, input: &str, decrypt: bool) -> String {
@@ -65,9 +65,9 @@ mod tests {
     #[test]
     fn identity() {
         let plaintext = "DIFFERENT DAY, SAME OLD NONSENSE";
-        let iden = Affine::new(&ENGLISH, 1, 0).unwrap();
-        assert_eq!(iden.encrypt(&plaintext), plaintext);
-        assert_eq!(iden.decrypt(&plaintext), plaintext);
+        let iden = Affine::new(ENGLISH, 1, 0).unwrap();
+        assert_eq!(iden.encrypt(plaintext), plaintext);
+        assert_eq!(iden.decrypt(plaintext), plaintext);
     }
 
     #[test]
@@ -75,43 +75,43 @@ mod tests {
         let plaintext = "THIS IS A SPOILER";
         let ciphertxt = "GUVF VF N FCBVYRE";
         let rot13 = Affine::new_rot13().unwrap();
-        assert_eq!(rot13.encrypt(&plaintext), ciphertxt);
-        assert_eq!(rot13.decrypt(&ciphertxt), plaintext);
+        assert_eq!(rot13.encrypt(plaintext), ciphertxt);
+        assert_eq!(rot13.decrypt(ciphertxt), plaintext);
     }
 
     #[test]
     fn caesar12() {
         let plaintext = "THE die IS CAST."; // case-sensitive!
         let ciphertxt = "FTQ die UE OMEF.";
-        let caesar = Affine::new_caesar(&ENGLISH, 12).unwrap();
-        assert_eq!(caesar.encrypt(&plaintext), ciphertxt);
-        assert_eq!(caesar.decrypt(&ciphertxt), plaintext);
+        let caesar = Affine::new_caesar(ENGLISH, 12).unwrap();
+        assert_eq!(caesar.encrypt(plaintext), ciphertxt);
+        assert_eq!(caesar.decrypt(ciphertxt), plaintext);
     }
 
     #[test]
     fn caesar27() {
         let plaintext = "ET TU, brute?"; // case-sensitive!
         let ciphertxt = "FU UV, brute?";
-        let caesar = Affine::new_caesar(&ENGLISH, 27).unwrap();
-        assert_eq!(caesar.encrypt(&plaintext), ciphertxt);
-        assert_eq!(caesar.decrypt(&ciphertxt), plaintext);
+        let caesar = Affine::new_caesar(ENGLISH, 27).unwrap();
+        assert_eq!(caesar.encrypt(plaintext), ciphertxt);
+        assert_eq!(caesar.decrypt(ciphertxt), plaintext);
     }
 
     #[test]
     fn atbash() {
         let plaintext = "PALINDROME";
         let ciphertxt = "KZORMWILNV";
-        let atbash = Affine::new_atbash(&ENGLISH).unwrap();
-        assert_eq!(atbash.encrypt(&plaintext), ciphertxt);
-        assert_eq!(atbash.decrypt(&ciphertxt), plaintext);
+        let atbash = Affine::new_atbash(ENGLISH).unwrap();
+        assert_eq!(atbash.encrypt(plaintext), ciphertxt);
+        assert_eq!(atbash.decrypt(ciphertxt), plaintext);
     }
 
     #[test]
     fn affine() {
         let plaintext = "MICHAEL"; // case-sensitive!
         let ciphertxt = "PZBITJY";
-        let affine = Affine::new(&ENGLISH, 17, 19).unwrap();
-        assert_eq!(affine.encrypt(&plaintext), ciphertxt);
-        assert_eq!(affine.decrypt(&ciphertxt), plaintext);
+        let affine = Affine::new(ENGLISH, 17, 19).unwrap();
+        assert_eq!(affine.encrypt(plaintext), ciphertxt);
+        assert_eq!(affine.decrypt(ciphertxt), plaintext);
     }
 }
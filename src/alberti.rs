@@ -0,0 +1,159 @@
+use crate::common::{check_unique, Cipher};
+use std::str::FromStr;
+
+pub const STABILIS: &str = "ABCDEFGILMNOPQRSTVXZ1234";
+pub const MOBILIS: &str = "gklnprtvz&xysomqihfdbace";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Signal {
+    // a clear outer letter sets the index letter under it
+    Index,
+    // an enciphered digit brings its cipher letter under the first outer letter
+    Digit,
+}
+impl FromStr for Signal {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "index" => Ok(Signal::Index),
+            "digit" => Ok(Signal::Digit),
+            _ => Err(format!("Unknown signal: {}", s)),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Alberti {
+    outer: Vec<char>,
+    inner: Vec<char>,
+    index: usize,
+    keys: Vec<usize>,
+    period: usize,
+    signal: Signal,
+}
+impl Alberti {
+    pub fn new(
+        outer: &str,
+        inner: &str,
+        index: char,
+        keys: &str,
+        period: usize,
+        signal: Signal,
+    ) -> Result<Self, String> {
+        check_unique(outer).unwrap();
+        check_unique(inner)?;
+        let (outer, inner): (Vec<char>, Vec<char>) =
+            (outer.chars().collect(), inner.chars().collect());
+        if outer.len() != inner.len() || outer.iter().any(|c| inner.contains(c)) {
+            return Err(String::from(
+                "Disks must be the same size with no characters in common!",
+            ));
+        }
+        let index = inner
+            .iter()
+            .position(|&c| c == index)
+            .ok_or(String::from("Index letter is not on the inner disk!"))?;
+        let keys = keys
+            .chars()
+            .map(|c| outer.iter().position(|&x| x == c))
+            .collect::<Option<Vec<usize>>>()
+            .ok_or(String::from("Keys contain invalid characters!"))?;
+        if keys.is_empty() || period == 0 {
+            return Err(String::from("Keys and period must not be empty!"));
+        }
+        if signal == Signal::Digit && !keys.iter().all(|&k| outer[k].is_ascii_digit()) {
+            return Err(String::from("Digit signals must be outer digits!"));
+        }
+        Ok(Self {
+            outer,
+            inner,
+            index,
+            keys,
+            period,
+            signal,
+        })
+    }
+
+    fn position(disk: &[char], c: char) -> Option<usize> {
+        disk.iter().position(|&x| x == c)
+    }
+}
+impl Cipher for Alberti {
+    fn encrypt(&self, plaintext: &str) -> String {
+        let n = self.outer.len();
+        let mut shift = self.index;
+        let mut output = String::new();
+        let letters = plaintext
+            .chars()
+            .filter_map(|c| Self::position(&self.outer, c));
+        for (i, p) in letters.enumerate() {
+            if i % self.period == 0 {
+                let key = self.keys[(i / self.period) % self.keys.len()];
+                match self.signal {
+                    Signal::Index => {
+                        shift = (self.index + n - key) % n;
+                        output.push(self.outer[key]);
+                    }
+                    Signal::Digit => {
+                        let j = (key + shift) % n;
+                        output.push(self.inner[j]);
+                        shift = j;
+                    }
+                }
+            }
+            output.push(self.inner[(p + shift) % n]);
+        }
+        output
+    }
+    fn decrypt(&self, ciphertxt: &str) -> String {
+        let n = self.outer.len();
+        let mut shift = self.index;
+        let mut output = String::new();
+        for c in ciphertxt.chars() {
+            if let Some(key) = Self::position(&self.outer, c) {
+                shift = (self.index + n - key) % n;
+            } else if let Some(j) = Self::position(&self.inner, c) {
+                let p = (j + n - shift) % n;
+                match self.signal == Signal::Digit && self.outer[p].is_ascii_digit() {
+                    true => shift = j,
+                    false => output.push(self.outer[p]),
+                }
+            }
+        }
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::ENGLISH;
+
+    #[test]
+    fn index() {
+        let alberti = Alberti::new(STABILIS, MOBILIS, 'k', "DG", 4, Signal::Index).unwrap();
+        let ciphertxt = alberti.encrypt("LA GVERRA");
+        assert_eq!(ciphertxt, "DtcpqGe&&d");
+        assert_eq!(alberti.decrypt(&ciphertxt), "LAGVERRA");
+        assert_eq!(alberti.decrypt("Dtc pq Ge& &d"), "LAGVERRA");
+    }
+
+    #[test]
+    fn digit() {
+        let alberti = Alberti::new(STABILIS, MOBILIS, 'g', "314", 5, Signal::Digit).unwrap();
+        let plaintext = "ILNEMICOVIENEDALLAPORTAORIENTALE";
+        let ciphertxt = alberti.encrypt(plaintext);
+        assert_eq!(ciphertxt.chars().count(), plaintext.len() + 7);
+        assert_eq!(alberti.decrypt(&ciphertxt), plaintext);
+    }
+
+    #[test]
+    fn invalid() {
+        let inner = "zyxwvutsrqponmlkjihgfedcba";
+        assert!(Alberti::new(ENGLISH, inner, 'k', "AB", 3, Signal::Index).is_ok());
+        assert!(Alberti::new(ENGLISH, inner, 'k', "AB", 3, Signal::Digit).is_err());
+        assert!(Alberti::new(STABILIS, inner, 'k', "AB", 3, Signal::Index).is_err());
+        assert!(Alberti::new(STABILIS, MOBILIS, 'K', "AB", 3, Signal::Index).is_err());
+        assert_eq!("digit".parse::<Signal>(), Ok(Signal::Digit));
+    }
+}
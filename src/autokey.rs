@@ -24,8 +24,8 @@ impl AutoKey {
         let keystream = self.primer.to_owned() + &filter(input, &self.alphabet);
         let vig = Vigenere::new(&self.alphabet, &keystream).unwrap();
         match self.autoregressive {
-            false => vig.encrypt(&input),
-            true => vig.decrypt(&input),
+            false => vig.encrypt(input),
+            true => vig.decrypt(input),
         }
     }
     fn autoregress(&self, input: &str) -> String {
@@ -37,8 +37,8 @@ impl AutoKey {
             let chunk = &input_filtered[i..std::cmp::min(i + chunk_size, input_len)];
             let vig = Vigenere::new(&self.alphabet, &key).unwrap();
             let substitute = match self.autoregressive {
-                false => vig.decrypt(&chunk),
-                true => vig.encrypt(&chunk),
+                false => vig.decrypt(chunk),
+                true => vig.encrypt(chunk),
             };
             output.push_str(&substitute);
             key = substitute;
@@ -72,16 +72,16 @@ mod tests {
     #[test]
     fn txtautokey() {
         let ciphertxt = "QNXEPV YT WTWP";
-        let autovig = AutoKey::new(&ENGLISH, "QUEENLY", false).unwrap();
-        assert_eq!(autovig.encrypt(&STRATEGY), ciphertxt);
-        assert_eq!(autovig.decrypt(&ciphertxt), STRATEGY);
+        let autovig = AutoKey::new(ENGLISH, "QUEENLY", false).unwrap();
+        assert_eq!(autovig.encrypt(STRATEGY), ciphertxt);
+        assert_eq!(autovig.decrypt(ciphertxt), STRATEGY);
     }
 
     #[test]
     fn keyautokey() {
         let ciphertxt = "QNXEPV YJ QXAC";
-        let autovig = AutoKey::new(&ENGLISH, "QUEENLY", true).unwrap();
-        assert_eq!(autovig.encrypt(&STRATEGY), ciphertxt);
-        assert_eq!(autovig.decrypt(&ciphertxt), STRATEGY);
+        let autovig = AutoKey::new(ENGLISH, "QUEENLY", true).unwrap();
+        assert_eq!(autovig.encrypt(STRATEGY), ciphertxt);
+        assert_eq!(autovig.decrypt(ciphertxt), STRATEGY);
     }
 }
@@ -0,0 +1,196 @@
+use crate::common::{char_index, check_unique, filter, Cipher};
+
+pub const BACON: &str = "ABCDEFGHIKLMNOPQRSTUWXYZ";
+pub const SYMBOLS: &str = "AB";
+
+const BOLD_UPPER: u32 = 0x1D400;
+const BOLD_LOWER: u32 = 0x1D41A;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Carrier {
+    // lowercase is A, uppercase is B
+    Case,
+    // plain is A, mathematical bold is B
+    Font,
+}
+impl Carrier {
+    fn classify(&self, c: char) -> Option<bool> {
+        match self {
+            Carrier::Case if c.is_ascii_alphabetic() => Some(c.is_ascii_uppercase()),
+            Carrier::Font if c.is_ascii_alphabetic() => Some(false),
+            Carrier::Font => unbold(c).map(|_| true),
+            _ => None,
+        }
+    }
+    fn style(&self, c: char, b: bool) -> char {
+        let plain = unbold(c).unwrap_or(c);
+        match (self, b) {
+            (Carrier::Case, false) => plain.to_ascii_lowercase(),
+            (Carrier::Case, true) => plain.to_ascii_uppercase(),
+            (Carrier::Font, false) => plain,
+            (Carrier::Font, true) => bold(plain),
+        }
+    }
+}
+
+fn bold(c: char) -> char {
+    let base = match c {
+        'A'..='Z' => BOLD_UPPER + (c as u32 - 'A' as u32),
+        'a'..='z' => BOLD_LOWER + (c as u32 - 'a' as u32),
+        _ => return c,
+    };
+    char::from_u32(base).unwrap_or(c)
+}
+fn unbold(c: char) -> Option<char> {
+    let code = c as u32;
+    match code {
+        _ if (BOLD_UPPER..BOLD_UPPER + 26).contains(&code) => {
+            char::from_u32('A' as u32 + code - BOLD_UPPER)
+        }
+        _ if (BOLD_LOWER..BOLD_LOWER + 26).contains(&code) => {
+            char::from_u32('a' as u32 + code - BOLD_LOWER)
+        }
+        _ => None,
+    }
+}
+
+#[derive(Debug)]
+pub struct Baconian {
+    alphabet: String,
+}
+impl Baconian {
+    pub fn new(alphabet: &str) -> Result<Self, String> {
+        check_unique(alphabet).unwrap();
+        if alphabet.chars().count() > 32 {
+            return Err(String::from("Alphabet must not exceed 32 characters!"));
+        }
+        Ok(Self {
+            alphabet: alphabet.to_string(),
+        })
+    }
+
+    fn merge(&self, input: &str) -> String {
+        input
+            .chars()
+            .map(|c| match c {
+                'J' if !self.alphabet.contains('J') => 'I',
+                'V' if !self.alphabet.contains('V') => 'U',
+                _ => c,
+            })
+            .collect()
+    }
+    fn bits(&self, plaintext: &str) -> Vec<bool> {
+        let clean = filter(&self.merge(plaintext), &self.alphabet);
+        char_index(&clean, &self.alphabet)
+            .into_iter()
+            .flat_map(|i| (0..5).rev().map(move |k| (i >> k) & 1 == 1))
+            .collect()
+    }
+    fn letters(&self, bits: &[bool]) -> String {
+        bits.chunks_exact(5)
+            .filter_map(|quintet| {
+                let i = quintet.iter().fold(0, |acc, &b| acc << 1 | b as usize);
+                self.alphabet.chars().nth(i)
+            })
+            .collect()
+    }
+
+    pub fn hide(&self, message: &str, carrier: &str, style: Carrier) -> Result<String, String> {
+        let bits = self.bits(message);
+        let capacity = carrier
+            .chars()
+            .filter(|&c| style.classify(c).is_some())
+            .count();
+        if capacity < bits.len() {
+            return Err(format!(
+                "Carrier holds {} symbols but the message needs {}!",
+                capacity,
+                bits.len()
+            ));
+        }
+        // carrier letters past the end of the message are all set to A
+        let mut stream = bits.into_iter();
+        Ok(carrier
+            .chars()
+            .map(|c| match style.classify(c) {
+                Some(_) => style.style(c, stream.next().unwrap_or(false)),
+                None => c,
+            })
+            .collect())
+    }
+    pub fn reveal(&self, text: &str, style: Carrier) -> String {
+        let bits: Vec<bool> = text.chars().filter_map(|c| style.classify(c)).collect();
+        self.letters(&bits)
+    }
+}
+impl Cipher for Baconian {
+    fn encrypt(&self, plaintext: &str) -> String {
+        let symbols: String = self
+            .bits(plaintext)
+            .into_iter()
+            .map(|b| match b {
+                false => 'A',
+                true => 'B',
+            })
+            .collect();
+        let quintets: Vec<&str> = (0..symbols.len())
+            .step_by(5)
+            .map(|i| &symbols[i..i + 5])
+            .collect();
+        quintets.join(" ")
+    }
+    fn decrypt(&self, ciphertxt: &str) -> String {
+        let bits: Vec<bool> = filter(ciphertxt, SYMBOLS)
+            .chars()
+            .map(|c| c == 'B')
+            .collect();
+        self.letters(&bits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::ENGLISH;
+
+    #[test]
+    fn classic() {
+        let baconian = Baconian::new(BACON).unwrap();
+        assert_eq!(baconian.encrypt("JAVA"), "ABAAA AAAAA BAABB AAAAA");
+        assert_eq!(baconian.decrypt("ABAAA AAAAA BAABB AAAAA"), "IAUA");
+        assert_eq!(baconian.encrypt("z"), "");
+        assert_eq!(baconian.encrypt("Z"), "BABBB");
+    }
+
+    #[test]
+    fn distinct() {
+        let baconian = Baconian::new(ENGLISH).unwrap();
+        let ciphertxt = baconian.encrypt("JAVA VIBE");
+        assert_eq!(ciphertxt, "ABAAB AAAAA BABAB AAAAA BABAB ABAAA AAAAB AABAA");
+        assert_eq!(baconian.decrypt(&ciphertxt), "JAVAVIBE");
+        assert!(Baconian::new(&format!("{}0123456", ENGLISH)).is_err());
+    }
+
+    #[test]
+    fn case() {
+        let baconian = Baconian::new(BACON).unwrap();
+        let carrier = "The quick brown fox, jumping over the lazy dog.";
+        let hidden = baconian.hide("FLEE", carrier, Carrier::Case).unwrap();
+        assert_eq!(hidden, "thE qUiCk BrowN fox, jUmping over the lazy dog.");
+        assert_eq!(hidden.to_lowercase(), carrier.to_lowercase());
+        assert_eq!(baconian.reveal(&hidden, Carrier::Case)[..4], *"FLEE");
+        assert!(baconian
+            .hide("FLEE AT ONCE", carrier, Carrier::Case)
+            .is_err());
+    }
+
+    #[test]
+    fn font() {
+        let baconian = Baconian::new(ENGLISH).unwrap();
+        let carrier = "Now is the winter of our discontent, made glorious summer.";
+        let hidden = baconian.hide("YORK", carrier, Carrier::Font).unwrap();
+        assert!(hidden.contains(", "));
+        assert_eq!(hidden.chars().filter(|&c| unbold(c).is_some()).count(), 9);
+        assert_eq!(baconian.reveal(&hidden, Carrier::Font)[..4], *"YORK");
+    }
+}
@@ -0,0 +1,116 @@
+// by code, first impulse as the high bit; `_` has no character in this shift
+pub const LETTERS: &str = "_T_O_HNM_LRGIPCVEZDBSYFXAWJ_UQK_";
+pub const FIGURES: &str = "_5_9__,._)4_80:=3+_?'6_/-2__71(_";
+// Bletchley Park tape notation
+pub const TAPE: &str = "/T3O9HNM4LRGIPCVEZDBSYFXAWJ8UQK+";
+
+pub const NULL: u8 = 0b00000;
+pub const SPACE: u8 = 0b00100;
+pub const CR: u8 = 0b00010;
+pub const LF: u8 = 0b01000;
+pub const FIGS: u8 = 0b11011;
+pub const LTRS: u8 = 0b11111;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Shift {
+    Letters,
+    Figures,
+}
+
+fn lookup(table: &str, c: char) -> Option<u8> {
+    match c {
+        '_' => None,
+        _ => table.chars().position(|x| x == c).map(|i| i as u8),
+    }
+}
+
+// newlines become CR LF; characters without a code are dropped
+pub fn encode(input: &str) -> Vec<u8> {
+    let mut shift = Shift::Letters;
+    let mut codes = Vec::new();
+    for c in input.to_uppercase().chars() {
+        let (code, needs) = match c {
+            ' ' => (Some(SPACE), None),
+            '\n' => {
+                codes.extend([CR, LF]);
+                continue;
+            }
+            _ => match lookup(LETTERS, c) {
+                Some(code) => (Some(code), Some(Shift::Letters)),
+                None => (lookup(FIGURES, c), Some(Shift::Figures)),
+            },
+        };
+        let Some(code) = code else {
+            continue;
+        };
+        match needs {
+            Some(needs) if needs != shift => {
+                codes.push(match needs {
+                    Shift::Letters => LTRS,
+                    Shift::Figures => FIGS,
+                });
+                shift = needs;
+            }
+            _ => {}
+        }
+        codes.push(code);
+    }
+    codes
+}
+
+pub fn decode(codes: &[u8]) -> String {
+    let mut shift = Shift::Letters;
+    let mut output = String::new();
+    for &code in codes {
+        match code & 0b11111 {
+            NULL | CR => {}
+            SPACE => output.push(' '),
+            LF => output.push('\n'),
+            FIGS => shift = Shift::Figures,
+            LTRS => shift = Shift::Letters,
+            code => {
+                let table = match shift {
+                    Shift::Letters => LETTERS,
+                    Shift::Figures => FIGURES,
+                };
+                match table.chars().nth(code as usize) {
+                    Some('_') | None => {}
+                    Some(c) => output.push(c),
+                }
+            }
+        }
+    }
+    output
+}
+
+pub fn to_tape(codes: &[u8]) -> String {
+    codes
+        .iter()
+        .map(|&code| TAPE.chars().nth((code & 0b11111) as usize).unwrap())
+        .collect()
+}
+
+pub fn from_tape(tape: &str) -> Vec<u8> {
+    tape.chars().filter_map(|c| lookup(TAPE, c)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shifts() {
+        let codes = encode("Send 25 tanks.");
+        assert_eq!(to_tape(&codes), "SEND98WT9+TANKS8M");
+        assert_eq!(decode(&codes), "SEND 25 TANKS.");
+        assert_eq!(decode(&encode("A\nB#")), "A\nB");
+    }
+
+    #[test]
+    fn tape() {
+        assert_eq!(TAPE.chars().count(), 32);
+        let codes: Vec<u8> = (0..32).collect();
+        assert_eq!(from_tape(&to_tape(&codes)), codes);
+        assert_eq!(from_tape("A 9"), [0b11000, SPACE]);
+    }
+}
@@ -1,9 +1,34 @@
 use clap::{Parser, Subcommand};
 use sigaba::affine::Affine;
+use sigaba::alberti::{self, Alberti, Signal};
 use sigaba::autokey::AutoKey;
+use sigaba::baconian::{self, Baconian, Carrier};
+use sigaba::bombe::Bombe;
+use sigaba::chaocipher::Chaocipher;
 use sigaba::common::Cipher;
-use sigaba::common::ENGLISH;
+use sigaba::common::{filter, ENGLISH};
+use sigaba::cylinder::{Cylinder, M94};
+use sigaba::digraph::{FourSquare, Slidefair, TwoSquare};
+use sigaba::enigma::Enigma;
+use sigaba::feistel::{self, Feistel, Mode};
+use sigaba::grandpre::Grandpre;
+use sigaba::gromark::Gromark;
+use sigaba::homophonic::Homophonic;
+use sigaba::kryptos::{self, Search, Section};
+use sigaba::lorenz::Lorenz;
+use sigaba::m209::M209;
+use sigaba::morse::{FractionatedMorse, Morbit, Pollux};
+use sigaba::nicodemus::Nicodemus;
+use sigaba::polybius::{Phillips, POLYBIUS};
+use sigaba::ragbaby::{Ragbaby, RAGBABY};
+use sigaba::rotor::Machine;
+use sigaba::solitaire::Solitaire;
+use sigaba::substitution::Substitution;
+use sigaba::syllabary::Syllabary;
+use sigaba::tapcode::{Separators, Style, TapCode, TAP};
 use sigaba::transpose::Transpose;
+use sigaba::typex::{self, Typex};
+use sigaba::vic::{self, Checkerboard, Vic};
 use sigaba::vigenere::Vigenere;
 use std::fs;
 use std::process::exit;
@@ -16,6 +41,18 @@ enum CipherT {
         #[arg(short, long)]
         offset: isize,
     },
+    Alberti {
+        #[arg(short = 'k', long)]
+        inner: Option<String>,
+        #[arg(short = 'x', long, default_value_t = 'k')]
+        index: char,
+        #[arg(short = 'K', long)]
+        keys: String,
+        #[arg(short = 'p', long, default_value_t = 5)]
+        period: usize,
+        #[arg(short = 'm', long, default_value = "index")]
+        signal: Signal,
+    },
     Atbash,
     AutoKey {
         #[arg(short = 'k', long)]
@@ -23,6 +60,14 @@ enum CipherT {
         #[arg(short = 'r', long, default_value_t = false)]
         auto: bool,
     },
+    Baconian {
+        #[arg(short = 'c', long, default_value_t = false)]
+        classic: bool,
+        #[arg(short = 't', long)]
+        carrier: Option<String>,
+        #[arg(short = 'f', long, default_value_t = false)]
+        font: bool,
+    },
     Beaufort {
         #[arg(short = 'k', long)]
         key: String,
@@ -31,7 +76,163 @@ enum CipherT {
         #[arg(short = 'b', long)]
         shift: isize,
     },
+    Chaocipher {
+        #[arg(short = 'k', long)]
+        key1: String,
+        #[arg(short = 'K', long)]
+        key2: String,
+    },
+    Checkerboard {
+        #[arg(short = 'k', long)]
+        header: String,
+        #[arg(short = 't', long, default_value = vic::TOP_ROW)]
+        top: String,
+    },
+    Cylinder {
+        #[arg(short = 'k', long, value_delimiter = ',')]
+        order: Vec<usize>,
+        #[arg(short = 'o', long, default_value_t = 1)]
+        offset: usize,
+        #[arg(short = 'c', long)]
+        crib: Option<String>,
+    },
+    Enigma {
+        #[arg(short = 'k', long, default_value = "I II III")]
+        order: String,
+        #[arg(short = 'x', long, default_value = "B")]
+        reflector: String,
+        #[arg(short = 'r', long, default_value = "AAA")]
+        rings: String,
+        #[arg(short = 'p', long, default_value = "AAA")]
+        positions: String,
+        #[arg(short = 'P', long, default_value = "")]
+        plugboard: String,
+        #[arg(short = 'c', long)]
+        crib: Option<String>,
+        #[arg(short = 'o', long, default_value_t = 0)]
+        offset: usize,
+    },
+    Feistel {
+        #[arg(short = 'k', long)]
+        key: String,
+        #[arg(short = 'r', long, default_value_t = 8)]
+        rounds: usize,
+        #[arg(short = 'b', long, default_value_t = 8)]
+        block_size: usize,
+        #[arg(short = 'm', long, default_value = "ecb")]
+        mode: Mode,
+        #[arg(short = 'f', long, default_value = "mix")]
+        round: String,
+        #[arg(short = 'v', long)]
+        iv: Option<String>,
+    },
+    FourSquare {
+        #[arg(short = 'k', long)]
+        key1: String,
+        #[arg(short = 'K', long)]
+        key2: String,
+        #[arg(short = 'x', long, default_value_t = 'X')]
+        null: char,
+    },
+    FractionatedMorse {
+        #[arg(short = 'k', long)]
+        key: String,
+    },
+    Grandpre {
+        #[arg(short = 'k', long)]
+        square: String,
+        #[arg(short = 's', long)]
+        seed: Option<u64>,
+        #[arg(short = 'r', long, default_value_t = false)]
+        rotate: bool,
+    },
+    Gromark {
+        #[arg(short = 'k', long)]
+        key: String,
+        #[arg(short = 'p', long)]
+        primer: String,
+    },
+    Homophonic {
+        #[arg(short = 'k', long)]
+        key: Option<String>,
+        #[arg(short = 't', long)]
+        table: Option<String>,
+        #[arg(short = 'm', long, default_value_t = 100)]
+        symbols: usize,
+        #[arg(short = 'r', long, default_value_t = 4)]
+        restarts: usize,
+        #[arg(short = 'n', long, default_value_t = 30000)]
+        iterations: usize,
+        #[arg(short = 's', long)]
+        seed: Option<u64>,
+    },
+    Kryptos {
+        #[arg(short = 'k', long, default_value = "KRYPTOS")]
+        alphabets: String,
+        #[arg(short = 'n', long, default_value_t = 12)]
+        period: usize,
+        #[arg(short = 'c', long, default_value = "")]
+        columns: String,
+        #[arg(short = 'w', long, default_value_t = 0)]
+        width: usize,
+        #[arg(short = 's', long)]
+        section: Option<Section>,
+    },
+    Lorenz {
+        #[arg(short = 'k', long)]
+        key_list: Option<String>,
+        #[arg(short = 'p', long)]
+        start: Option<String>,
+        #[arg(short = 's', long)]
+        seed: Option<u64>,
+        #[arg(short = 'c', long, default_value_t = false)]
+        find_chi: bool,
+    },
+    M209 {
+        #[arg(short = 'k', long)]
+        key_list: Option<String>,
+        #[arg(short = 'p', long)]
+        position: Option<String>,
+        #[arg(short = 'c', long)]
+        crib: Option<String>,
+        #[arg(short = 'n', long, default_value_t = 20000)]
+        iterations: usize,
+        #[arg(short = 's', long)]
+        seed: Option<u64>,
+    },
+    Morbit {
+        #[arg(short = 'k', long)]
+        key: String,
+    },
+    Nicodemus {
+        #[arg(short = 'k', long)]
+        key: String,
+    },
+    Phillips {
+        #[arg(short = 'k', long)]
+        key: String,
+    },
+    PeriodicGromark {
+        #[arg(short = 'k', long)]
+        key: String,
+    },
+    Pollux {
+        #[arg(short = 'k', long)]
+        key: String,
+        #[arg(short = 's', long)]
+        seed: Option<u64>,
+    },
+    Ragbaby {
+        #[arg(short = 'k', long)]
+        key: String,
+    },
     Rot13,
+    Rotor {
+        #[arg(short = 'k', long)]
+        config: String,
+        #[arg(short = 'p', long)]
+        positions: Option<String>,
+    },
     Rotate {
         #[arg(short = 'n', long)]
         n_rows: usize,
@@ -46,6 +247,76 @@ enum CipherT {
         #[arg(short = 'p', long, default_value_t = false)]
         pad_cols: bool,
     },
+    Slidefair {
+        #[arg(short = 'k', long)]
+        key: String,
+        #[arg(short = 'x', long, default_value_t = 'X')]
+        null: char,
+    },
+    Solitaire {
+        #[arg(short = 'k', long, default_value = "")]
+        key: String,
+        #[arg(short = 'D', long)]
+        deck: Option<String>,
+        #[arg(short = 'S', long)]
+        save: Option<String>,
+    },
+    Substitution {
+        #[arg(short = 'k', long)]
+        key: Option<String>,
+        #[arg(short = 'r', long, default_value_t = 8)]
+        restarts: usize,
+        #[arg(short = 'n', long, default_value_t = 10000)]
+        iterations: usize,
+        #[arg(short = 's', long)]
+        seed: Option<u64>,
+    },
+    Syllabary {
+        #[arg(short = 'k', long)]
+        key1: String,
+        #[arg(short = 'K', long)]
+        key2: String,
+    },
+    TapCode {
+        #[arg(short = 'm', long, default_value = "digits")]
+        style: Style,
+        #[arg(short = 'P', long)]
+        pair: Option<String>,
+        #[arg(short = 'L', long)]
+        letter: Option<String>,
+        #[arg(short = 'W', long)]
+        word: Option<String>,
+    },
+    TwoSquare {
+        #[arg(short = 'k', long)]
+        key1: String,
+        #[arg(short = 'K', long)]
+        key2: String,
+        #[arg(short = 'x', long, default_value_t = 'X')]
+        null: char,
+        #[arg(short = 'v', long, default_value_t = false)]
+        vertical: bool,
+    },
+    Typex {
+        #[arg(short = 'k', long, default_value = "1 2 3 4 5")]
+        order: String,
+        #[arg(short = 'r', long, default_value = "AAAAA")]
+        rings: String,
+        #[arg(short = 'p', long, default_value = "AAAAA")]
+        positions: String,
+        #[arg(short = 'x', long, default_value = typex::REFLECTOR)]
+        reflector: String,
+    },
+    Vic {
+        #[arg(short = 'k', long)]
+        phrase: String,
+        #[arg(short = 't', long)]
+        date: String,
+        #[arg(short = 'p', long)]
+        personal: usize,
+        #[arg(short = 'm', long)]
+        indicator: Option<String>,
+    },
     Vigenere {
         #[arg(short = 'k', long)]
         key: String,
@@ -78,14 +349,352 @@ fn main() {
     let args = Args::parse();
 
     let alphabet = &args.alphabet;
+    let square = match alphabet.as_str() {
+        ENGLISH => POLYBIUS,
+        _ => alphabet,
+    };
 
+    let input = match fs::read_to_string(&args.input_file) {
+        Ok(file_content) => {
+            if !args.input.is_empty() {
+                eprintln!("W: Both file and direct input provided. The file content will be used.");
+            }
+            file_content
+        }
+        Err(_) if !args.input.is_empty() => args.input.clone(),
+        Err(_) if matches!(args.cipher, CipherT::Kryptos { .. }) => kryptos::K4.to_string(),
+        Err(_) => {
+            eprintln!("E: No input provided.");
+            exit(1);
+        }
+    };
+
+    let mut decrypt = args.decrypt;
+    let mut feistel;
+    let bacon;
+    let mut m209;
+    let mut lorenz;
+    let solitaire;
+    let mut machine;
     let cipher: &dyn Cipher = match &args.cipher {
         CipherT::Affine { factor, offset } => &Affine::new(alphabet, *factor, *offset).unwrap(),
+        CipherT::Alberti {
+            inner,
+            index,
+            keys,
+            period,
+            signal,
+        } => {
+            let (outer, inner) = match inner {
+                Some(inner) => (alphabet.as_str(), inner.as_str()),
+                None => (alberti::STABILIS, alberti::MOBILIS),
+            };
+            &Alberti::new(outer, inner, *index, keys, *period, *signal).unwrap()
+        }
         CipherT::Atbash => &Affine::new_atbash(alphabet).unwrap(),
         CipherT::AutoKey { key, auto } => &AutoKey::new(alphabet, key, *auto).unwrap(),
-        CipherT::Beaufort { key } => &Vigenere::new_beaufort(alphabet, &key).unwrap(),
+        CipherT::Baconian {
+            classic,
+            carrier,
+            font,
+        } => {
+            bacon = Baconian::new(match *classic {
+                false => alphabet,
+                true => baconian::BACON,
+            })
+            .unwrap();
+            let style = match *font {
+                false => Carrier::Case,
+                true => Carrier::Font,
+            };
+            let output = match (carrier, decrypt) {
+                (None, _) => None,
+                (Some(carrier), false) => {
+                    Some(bacon.hide(&input, carrier, style).unwrap_or_else(|e| {
+                        eprintln!("E: {}", e);
+                        exit(1);
+                    }))
+                }
+                (Some(_), true) => Some(bacon.reveal(&input, style)),
+            };
+            match output {
+                Some(output) => return write_output(output, &args.output_file),
+                None => &bacon,
+            }
+        }
+        CipherT::Beaufort { key } => &Vigenere::new_beaufort(alphabet, key).unwrap(),
         CipherT::Caesar { shift } => &Affine::new_caesar(alphabet, *shift).unwrap(),
+        CipherT::Chaocipher { key1, key2 } => &Chaocipher::new(alphabet, key1, key2).unwrap(),
+        CipherT::Checkerboard { header, top } => &Checkerboard::new(alphabet, top, header).unwrap(),
+        CipherT::Cylinder {
+            order,
+            crib: Some(crib),
+            ..
+        } => {
+            let width = match order.len() {
+                0 => M94.len(),
+                n => n,
+            };
+            let solutions = Cylinder::bazeries(ENGLISH, &M94, width, crib, &input, 10).unwrap();
+            let lines: Vec<String> = solutions
+                .into_iter()
+                .map(|(offset, order)| {
+                    let order: Vec<String> = order
+                        .into_iter()
+                        .map(|d| d.map_or(String::from("?"), |d| d.to_string()))
+                        .collect();
+                    format!("offset {} order {}", offset, order.join(","))
+                })
+                .collect();
+            eprintln!("I: Found {} consistent disk orders.", lines.len());
+            return write_output(lines.join("\n"), &args.output_file);
+        }
+        CipherT::Cylinder { order, offset, .. } => &Cylinder::new_m94(order, *offset).unwrap(),
+        CipherT::Enigma {
+            reflector,
+            crib: Some(crib),
+            offset,
+            ..
+        } => {
+            let bombe = Bombe::new(&input, crib, *offset, reflector).unwrap();
+            let (menu, test) = bombe.menu();
+            eprintln!("I: Menu {} with test letter {}.", menu, test);
+            let lines: Vec<String> = bombe
+                .run_all()
+                .into_iter()
+                .map(|stop| {
+                    let steckers: Vec<String> = stop
+                        .steckers
+                        .iter()
+                        .map(|(a, b)| format!("{}{}", a, b))
+                        .collect();
+                    format!(
+                        "{} at {}: {}",
+                        stop.order,
+                        stop.positions,
+                        steckers.join(" ")
+                    )
+                })
+                .collect();
+            eprintln!("I: Found {} stops.", lines.len());
+            return write_output(lines.join("\n"), &args.output_file);
+        }
+        CipherT::Enigma {
+            order,
+            reflector,
+            rings,
+            positions,
+            plugboard,
+            ..
+        } => &Enigma::new(order, reflector, rings, positions, plugboard).unwrap(),
+        CipherT::Feistel {
+            key,
+            rounds,
+            block_size,
+            mode,
+            round,
+            iv,
+        } => {
+            let round = match round.as_str() {
+                "add" => feistel::additive,
+                "mix" => feistel::mixing,
+                _ => {
+                    eprintln!("E: unknown round function: {}", round);
+                    exit(1);
+                }
+            };
+            feistel =
+                Feistel::with_round(alphabet, key, *rounds, *block_size, *mode, round).unwrap();
+            if let Some(iv) = iv {
+                feistel.set_iv(iv).unwrap();
+            }
+            &feistel
+        }
+        CipherT::FourSquare { key1, key2, null } => {
+            &FourSquare::new(square, key1, key2, *null).unwrap()
+        }
+        CipherT::FractionatedMorse { key } => &FractionatedMorse::new(alphabet, key).unwrap(),
+        CipherT::Grandpre {
+            square,
+            seed,
+            rotate,
+        } => &(match *rotate {
+            false => Grandpre::new(alphabet, square, *seed),
+            true => Grandpre::new_rotating(alphabet, square),
+        })
+        .unwrap(),
+        CipherT::Gromark { key, primer } => &Gromark::new(alphabet, key, primer).unwrap(),
+        CipherT::Homophonic {
+            key: Some(key),
+            symbols,
+            ..
+        } => &Homophonic::new(alphabet, key, *symbols).unwrap(),
+        CipherT::Homophonic {
+            table: Some(table), ..
+        } => &Homophonic::from_key(alphabet, table).unwrap(),
+        CipherT::Homophonic {
+            restarts,
+            iterations,
+            seed,
+            ..
+        } => {
+            let solution = Homophonic::solve(alphabet, &input, *restarts, *iterations, *seed);
+            if solution.key.is_empty() {
+                eprintln!("E: No two-digit symbols to solve.");
+                exit(1);
+            }
+            eprintln!(
+                "I: Recovered key {} with confidence {:.2}.",
+                solution.key, solution.confidence
+            );
+            decrypt = true;
+            &Homophonic::from_key(alphabet, &solution.key).unwrap()
+        }
+        CipherT::Kryptos {
+            section: Some(section),
+            ..
+        } => {
+            let mut passed = true;
+            let lines: Vec<String> = section
+                .solved()
+                .into_iter()
+                .map(|section| {
+                    let mismatches = section.check();
+                    passed &= mismatches.is_empty();
+                    let result = match mismatches.first() {
+                        None => String::from("pass"),
+                        Some(first) => format!(
+                            "FAIL, {} letters differ from letter {}",
+                            mismatches.len(),
+                            first
+                        ),
+                    };
+                    format!(
+                        "=== {:?}: {} ===\n{}",
+                        section,
+                        result,
+                        section.decrypt().trim_end()
+                    )
+                })
+                .collect();
+            write_output(lines.join("\n"), &args.output_file);
+            if !passed {
+                eprintln!("E: Decryption differs from the bundled plaintext.");
+                exit(1);
+            }
+            return;
+        }
+        CipherT::Kryptos {
+            alphabets,
+            period,
+            columns,
+            width,
+            ..
+        } => {
+            let search = Search::new(alphabets, *period, columns, *width);
+            let lines: Vec<String> = search
+                .run(&input, &kryptos::CRIBS)
+                .into_iter()
+                .map(|c| {
+                    format!(
+                        "{:?} on {} with {}, period {} key {}: {}",
+                        c.variant, c.alphabet, c.transposition, c.period, c.key, c.plaintext
+                    )
+                })
+                .collect();
+            eprintln!("I: Found {} hypotheses matching the K4 cribs.", lines.len());
+            return write_output(lines.join("\n"), &args.output_file);
+        }
+        CipherT::Lorenz {
+            key_list,
+            start,
+            seed,
+            find_chi,
+        } => {
+            lorenz = match key_list {
+                Some(path) => {
+                    let key_list = fs::read_to_string(path).unwrap_or_else(|e| {
+                        eprintln!("E: failed to read key list: {}", e);
+                        exit(1);
+                    });
+                    Lorenz::from_key_list(&key_list).unwrap()
+                }
+                None => Lorenz::random(*seed),
+            };
+            if let Some(start) = start {
+                let start: Vec<usize> = start
+                    .split(|c: char| c == ',' || c.is_whitespace())
+                    .filter_map(|s| s.parse().ok())
+                    .collect();
+                lorenz.set_start(&start).unwrap();
+            }
+            if *find_chi {
+                let chi = lorenz.find_chi(&input);
+                eprintln!("I: Chi wheels set at {:?}.", chi);
+                let mut start = lorenz.start();
+                start[..5].copy_from_slice(&chi);
+                lorenz.set_start(&start).unwrap();
+                return write_output(lorenz.dechi(&input), &args.output_file);
+            }
+            &lorenz
+        }
+        CipherT::M209 {
+            key_list,
+            position,
+            crib,
+            iterations,
+            seed,
+        } => {
+            m209 = match (key_list, crib) {
+                (Some(path), _) => {
+                    let key_list = fs::read_to_string(path).unwrap_or_else(|e| {
+                        eprintln!("E: failed to read key list: {}", e);
+                        exit(1);
+                    });
+                    M209::from_key_list(&key_list).unwrap()
+                }
+                (None, Some(crib)) => {
+                    let start = position.as_deref().unwrap_or("AAAAAA");
+                    let recovered = M209::recover(crib, &input, start, *iterations, *seed);
+                    let recovered = recovered.unwrap();
+                    eprintln!("I: Recovered key list:\n{}", recovered.key_list());
+                    decrypt = true;
+                    recovered
+                }
+                (None, None) => {
+                    eprintln!("E: Either a key list or a crib is required.");
+                    exit(1);
+                }
+            };
+            if let Some(position) = position {
+                m209.set_position(position).unwrap();
+            }
+            &m209
+        }
+        CipherT::Morbit { key } => &Morbit::new(alphabet, key).unwrap(),
+        CipherT::Nicodemus { key } => &Nicodemus::new(alphabet, key).unwrap(),
+        CipherT::Phillips { key } => &Phillips::new(square, key).unwrap(),
+        CipherT::PeriodicGromark { key } => &Gromark::new_periodic(alphabet, key).unwrap(),
+        CipherT::Pollux { key, seed } => &Pollux::new(key, *seed).unwrap(),
+        CipherT::Ragbaby { key } => {
+            let alphabet = match alphabet.as_str() {
+                ENGLISH => RAGBABY,
+                _ => alphabet,
+            };
+            &Ragbaby::new(alphabet, key).unwrap()
+        }
         CipherT::Rot13 => &Affine::new_rot13().unwrap(),
+        CipherT::Rotor { config, positions } => {
+            let config = fs::read_to_string(config).unwrap_or_else(|e| {
+                eprintln!("E: failed to read config: {}", e);
+                exit(1);
+            });
+            machine = Machine::from_config(&config).unwrap();
+            if let Some(positions) = positions {
+                machine.set_positions(positions).unwrap();
+            }
+            &machine
+        }
         CipherT::Transpose { n_rows, pad_cols } => {
             &Transpose::as_flip(alphabet, *n_rows, *pad_cols).unwrap()
         }
@@ -98,33 +707,101 @@ fn main() {
             true => Transpose::as_left,
         })(alphabet, *n_rows, *pad_cols)
         .unwrap(),
-        CipherT::Vigenere { key } => &Vigenere::new(alphabet, &key).unwrap(),
-    };
-
-    let input = match fs::read_to_string(&args.input_file) {
-        Ok(file_content) => {
-            if !args.input.is_empty() {
-                println!("W: Both file and direct input provided. The file content will be used.");
+        CipherT::Slidefair { key, null } => &Slidefair::new(alphabet, key, *null).unwrap(),
+        CipherT::Solitaire { key, deck, save } => {
+            solitaire = match deck {
+                Some(path) => {
+                    let deck = fs::read_to_string(path).unwrap_or_else(|e| {
+                        eprintln!("E: failed to read deck: {}", e);
+                        exit(1);
+                    });
+                    Solitaire::from_deck(&deck).unwrap()
+                }
+                None => Solitaire::new(key),
+            };
+            if let Some(path) = save {
+                let mut next = solitaire.clone();
+                next.advance(filter(&input, ENGLISH).len());
+                match fs::write(path, next.deck()) {
+                    Ok(_) => eprintln!("I: Deck saved to {}.", path),
+                    Err(e) => eprintln!("W: failed to save deck: {}", e),
+                }
             }
-            file_content
+            &solitaire
         }
-        Err(_) if !args.input.is_empty() => args.input.clone(),
-        Err(_) => {
-            println!("E: No input provided.");
-            exit(1);
+        CipherT::Substitution { key: Some(key), .. } => &Substitution::new(alphabet, key).unwrap(),
+        CipherT::Substitution {
+            key: None,
+            restarts,
+            iterations,
+            seed,
+        } => {
+            let solution = Substitution::solve(alphabet, &input, *restarts, *iterations, *seed);
+            eprintln!(
+                "I: Recovered key {} with confidence {:.2}.",
+                solution.key, solution.confidence
+            );
+            decrypt = true;
+            &Substitution::new(alphabet, &solution.key).unwrap()
+        }
+        CipherT::Syllabary { key1, key2 } => &Syllabary::new(alphabet, key1, key2).unwrap(),
+        CipherT::TapCode {
+            style,
+            pair,
+            letter,
+            word,
+        } => {
+            let defaults = Separators::new(*style);
+            let separators = Separators {
+                pair: pair.clone().unwrap_or(defaults.pair),
+                letter: letter.clone().unwrap_or(defaults.letter),
+                word: word.clone().unwrap_or(defaults.word),
+            };
+            let alphabet = match alphabet.as_str() {
+                ENGLISH => TAP,
+                _ => alphabet,
+            };
+            &TapCode::new(alphabet, *style, separators).unwrap()
         }
+        CipherT::TwoSquare {
+            key1,
+            key2,
+            null,
+            vertical,
+        } => &(match *vertical {
+            false => TwoSquare::new,
+            true => TwoSquare::new_vertical,
+        })(square, key1, key2, *null)
+        .unwrap(),
+        CipherT::Typex {
+            order,
+            rings,
+            positions,
+            reflector,
+        } => &Typex::new(order, rings, positions, reflector).unwrap(),
+        CipherT::Vic {
+            phrase,
+            date,
+            personal,
+            indicator,
+        } => &Vic::new(phrase, date, *personal, indicator.as_deref()).unwrap(),
+        CipherT::Vigenere { key } => &Vigenere::new(alphabet, key).unwrap(),
     };
 
-    let output = match args.decrypt {
+    let output = match decrypt {
         false => cipher.encrypt(&input),
         true => cipher.decrypt(&input),
     };
 
-    if args.output_file.len() > 0 {
-        if let Err(e) = fs::write(&args.output_file, output) {
+    write_output(output, &args.output_file);
+}
+
+fn write_output(output: String, output_file: &str) {
+    if !output_file.is_empty() {
+        if let Err(e) = fs::write(output_file, output) {
             eprintln!("E: failed to write to file: {}", e);
         } else {
-            println!("File written successfully: {}", args.output_file);
+            eprintln!("File written successfully: {}", output_file);
         }
     } else {
         println!("{}", output);
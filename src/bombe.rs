@@ -0,0 +1,229 @@
+use crate::common::{filter, ENGLISH};
+use crate::enigma::{order_name, reflector, rotors};
+use crate::rotor::Reflector;
+use std::thread;
+
+const N: usize = 26;
+const FULL: u32 = (1 << N) - 1;
+
+// rotor order and start positions, assuming rings at A
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stop {
+    pub order: String,
+    pub positions: String,
+    pub steckers: Vec<(char, char)>,
+}
+
+// assumes, like the real bombe, that the middle rotor does not turn over within the crib
+#[derive(Debug)]
+pub struct Bombe {
+    reflector: Reflector,
+    // (a, b, step of its scrambler)
+    links: Vec<(usize, usize, usize)>,
+    // (other letter, link index)
+    adjacent: Vec<Vec<(usize, usize)>>,
+    test: usize,
+}
+impl Bombe {
+    pub fn new(
+        ciphertxt: &str,
+        crib: &str,
+        offset: usize,
+        reflector: &str,
+    ) -> Result<Self, String> {
+        let index = |s: &str| -> Vec<usize> {
+            filter(s, ENGLISH)
+                .chars()
+                .map(|c| ENGLISH.find(c).unwrap())
+                .collect()
+        };
+        let (cipher, crib) = (index(ciphertxt), index(crib));
+        if crib.is_empty() || offset + crib.len() > cipher.len() {
+            return Err(String::from("Crib must lie within the ciphertext!"));
+        }
+        let links: Vec<(usize, usize, usize)> = crib
+            .iter()
+            .zip(&cipher[offset..])
+            .enumerate()
+            .map(|(i, (&p, &c))| (p, c, offset + i))
+            .collect();
+        if links.iter().any(|&(p, c, _)| p == c) {
+            return Err(String::from("Crib places a letter under itself!"));
+        }
+        let mut adjacent = vec![Vec::new(); N];
+        for (i, &(p, c, _)) in links.iter().enumerate() {
+            adjacent[p].push((c, i));
+            adjacent[c].push((p, i));
+        }
+        let test = (0..N).max_by_key(|&a| adjacent[a].len()).unwrap();
+        Ok(Self {
+            reflector: self::reflector(reflector)?,
+            links,
+            adjacent,
+            test,
+        })
+    }
+
+    pub fn menu(&self) -> (String, char) {
+        let letter = |i: usize| ENGLISH.chars().nth(i).unwrap();
+        let links: Vec<String> = self
+            .links
+            .iter()
+            .map(|&(p, c, step)| format!("{}-{}@{}", letter(p), letter(c), step))
+            .collect();
+        (links.join(" "), letter(self.test))
+    }
+
+    // stops early once the test letter is fully lit
+    fn spread(&self, scramblers: &[[usize; N]], a: usize, b: usize) -> [u32; N] {
+        let mut live = [0u32; N];
+        let mut stack = vec![(a, b)];
+        while let Some((a, b)) = stack.pop() {
+            if live[a] & (1 << b) != 0 {
+                continue;
+            }
+            live[a] |= 1 << b;
+            live[b] |= 1 << a;
+            if live[self.test] == FULL {
+                break;
+            }
+            for &(c, i) in &self.adjacent[a] {
+                stack.push((c, scramblers[i][b]));
+            }
+            for &(c, i) in &self.adjacent[b] {
+                stack.push((c, scramblers[i][a]));
+            }
+        }
+        live
+    }
+
+    fn stops(&self, order: &[usize; 3]) -> Vec<Stop> {
+        let letter = |i: usize| ENGLISH.chars().nth(i).unwrap();
+        let mut rotors = rotors(order, "AAA", "AAA").unwrap();
+        let mut stops = Vec::new();
+        for (left, middle) in (0..N).flat_map(|l| (0..N).map(move |m| (l, m))) {
+            // the scrambler for each position of the fast rotor
+            let table: Vec<[usize; N]> = (0..N)
+                .map(|right| {
+                    for (rotor, position) in rotors.iter_mut().zip([left, middle, right]) {
+                        rotor.set_position(position);
+                    }
+                    let mut e = [0; N];
+                    for (y, out) in e.iter_mut().enumerate() {
+                        let c = rotors.iter().rev().fold(y, |c, r| r.forward(c));
+                        let c = self.reflector.reflect(c);
+                        *out = rotors.iter().fold(c, |c, r| r.backward(c));
+                    }
+                    e
+                })
+                .collect();
+            for right in 0..N {
+                let scramblers: Vec<[usize; N]> = self
+                    .links
+                    .iter()
+                    .map(|&(_, _, step)| table[(right + step + 1) % N])
+                    .collect();
+                let live = self.spread(&scramblers, self.test, 0);
+                let lit = live[self.test].count_ones();
+                if lit == N as u32 {
+                    continue;
+                }
+                let partner = match lit {
+                    25 => (!live[self.test] & FULL).trailing_zeros() as usize,
+                    _ => 0,
+                };
+                let live = self.spread(&scramblers, self.test, partner);
+                if live[self.test] == FULL {
+                    continue;
+                }
+                let steckers = (0..N)
+                    .filter(|&a| live[a].count_ones() == 1)
+                    .map(|a| (a, live[a].trailing_zeros() as usize))
+                    .filter(|&(a, b)| a < b)
+                    .map(|(a, b)| (letter(a), letter(b)))
+                    .collect();
+                stops.push(Stop {
+                    order: order_name(order),
+                    positions: [left, middle, right].map(letter).iter().collect(),
+                    steckers,
+                });
+            }
+        }
+        stops
+    }
+
+    pub fn run(&self, orders: &[[usize; 3]]) -> Vec<Stop> {
+        let n_threads = thread::available_parallelism().map_or(1, |n| n.get());
+        let chunk = orders.len().div_ceil(n_threads).max(1);
+        thread::scope(|scope| {
+            let handles: Vec<_> = orders
+                .chunks(chunk)
+                .map(|orders| {
+                    scope.spawn(move || {
+                        orders
+                            .iter()
+                            .flat_map(|o| self.stops(o))
+                            .collect::<Vec<Stop>>()
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().unwrap())
+                .collect()
+        })
+    }
+    pub fn run_all(&self) -> Vec<Stop> {
+        let orders: Vec<[usize; 3]> = (0..5)
+            .flat_map(|a| (0..5).flat_map(move |b| (0..5).map(move |c| [a, b, c])))
+            .filter(|[a, b, c]| a != b && b != c && a != c)
+            .collect();
+        self.run(&orders)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::Cipher;
+    use crate::enigma::{parse_order, Enigma};
+
+    const PLUGBOARD: &str = "AK BZ CR DO EU FT";
+    const CRIB: &str = "WETTERVORHERSAGEBISKAYA";
+
+    fn traffic() -> String {
+        // the fast rotor turns the middle one over only after the crib
+        let enigma = Enigma::new("II V III", "B", "AAA", "QDW", PLUGBOARD).unwrap();
+        enigma.encrypt(&format!("AN{}UMSECHSUHR", CRIB))
+    }
+
+    #[test]
+    fn menu() {
+        let ciphertxt = traffic();
+        let bombe = Bombe::new(&ciphertxt, CRIB, 2, "B").unwrap();
+        let (menu, test) = bombe.menu();
+        assert_eq!(menu.split(' ').count(), CRIB.len());
+        assert!(menu.starts_with(&format!("W-{}@2", &ciphertxt[2..3])));
+        assert!(CRIB.contains(test) || ciphertxt.contains(test));
+
+        assert!(Bombe::new(&ciphertxt, CRIB, 20, "B").is_err());
+        assert!(Bombe::new(&ciphertxt, &ciphertxt[..5], 0, "B").is_err());
+    }
+
+    #[test]
+    fn stops() {
+        let bombe = Bombe::new(&traffic(), CRIB, 2, "B").unwrap();
+        let orders = ["II V III", "I II III", "V IV II"].map(|o| parse_order(o).unwrap());
+        let stops = bombe.run(&orders);
+        assert!(!stops.is_empty() && stops.len() < 20);
+
+        let stop = stops.iter().find(|s| s.positions == "QDW").unwrap();
+        assert_eq!(stop.order, "II V III");
+        let plugged: Vec<(char, char)> = PLUGBOARD
+            .split(' ')
+            .map(|p| (p.chars().next().unwrap(), p.chars().nth(1).unwrap()))
+            .collect();
+        assert!(stop.steckers.iter().all(|s| plugged.contains(s)));
+        assert!(stop.steckers.len() >= 3);
+    }
+}
@@ -0,0 +1,100 @@
+use crate::common::{check_unique, filter, keyed_alphabet, refill, Cipher};
+
+fn permute(wheel: &mut Vec<char>, at: usize, extra: usize) {
+    let nadir = wheel.len() / 2;
+    wheel.rotate_left(at + extra);
+    let c = wheel.remove(1 + extra);
+    wheel.insert(nadir, c);
+}
+
+#[derive(Debug)]
+pub struct Chaocipher {
+    alphabet: String,
+    left: String,
+    right: String,
+}
+impl Chaocipher {
+    pub fn new(alphabet: &str, left: &str, right: &str) -> Result<Self, String> {
+        check_unique(alphabet).unwrap();
+        if alphabet.chars().count() < 4 {
+            return Err(String::from("Alphabet must have at least 4 characters!"));
+        }
+        if ![left, right]
+            .iter()
+            .all(|key| key.chars().all(|c| alphabet.contains(c)))
+        {
+            return Err(String::from("Keyword contains invalid characters!"));
+        }
+        Ok(Self {
+            alphabet: alphabet.to_string(),
+            left: keyed_alphabet(left, alphabet),
+            right: keyed_alphabet(right, alphabet),
+        })
+    }
+
+    fn substitute(&self, input: &str, decrypt: bool) -> String {
+        let mut left: Vec<char> = self.left.chars().collect();
+        let mut right: Vec<char> = self.right.chars().collect();
+        let output: String = filter(input, &self.alphabet)
+            .chars()
+            .map(|c| {
+                let at = match decrypt {
+                    false => right.iter().position(|&x| x == c).unwrap(),
+                    true => left.iter().position(|&x| x == c).unwrap(),
+                };
+                let out = match decrypt {
+                    false => left[at],
+                    true => right[at],
+                };
+                // the left (cipher) wheel moves its zenith+1 letter to the nadir;
+                // the right (plain) wheel turns one further and moves zenith+2
+                permute(&mut left, at, 0);
+                permute(&mut right, at, 1);
+                out
+            })
+            .collect();
+        refill(&output, input, &self.alphabet)
+    }
+}
+impl Cipher for Chaocipher {
+    fn encrypt(&self, plaintext: &str) -> String {
+        self.substitute(plaintext, false)
+    }
+    fn decrypt(&self, ciphertxt: &str) -> String {
+        self.substitute(ciphertxt, true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::{ENGLISH, KRYPTOS};
+
+    const LEFT: &str = "HXUCZVAMDSLKPEFJRIGTWOBNYQ";
+    const RIGHT: &str = "PTLNBQDEOYSFAVZKGJRIHWXUMC";
+
+    #[test]
+    fn exhibit() {
+        let chaocipher = Chaocipher::new(ENGLISH, LEFT, RIGHT).unwrap();
+        let plaintext = "WELLDONEISBETTERTHANWELLSAID";
+        let ciphertxt = "OAHQHCNYNXTSZJRRHJBYHQKSOUJY";
+        assert_eq!(chaocipher.encrypt(plaintext), ciphertxt);
+        assert_eq!(chaocipher.decrypt(ciphertxt), plaintext);
+    }
+
+    #[test]
+    fn keyed() {
+        let chaocipher = Chaocipher::new(ENGLISH, "KRYPTOS", "PALIMPSEST").unwrap();
+        assert_eq!(chaocipher.left, KRYPTOS);
+        let plaintext = "BETWEEN SUBTLE SHADING AND THE ABSENCE OF LIGHT";
+        let ciphertxt = chaocipher.encrypt(plaintext);
+        assert_ne!(ciphertxt[..7], plaintext[..7]);
+        assert_eq!(chaocipher.decrypt(&ciphertxt), plaintext);
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(Chaocipher::new(ENGLISH, "kryptos", RIGHT).is_err());
+        assert!(Chaocipher::new("ABC", "A", "B").is_err());
+    }
+}
@@ -7,6 +7,24 @@ pub fn filter(input: &str, alphabet: &str) -> String {
     input.chars().filter(|c| alphabet.contains(*c)).collect()
 }
 
+pub fn tokenize(input: &str, units: &[&str]) -> Vec<usize> {
+    let mut tokens = Vec::new();
+    let mut rest = input;
+    while let Some(c) = rest.chars().next() {
+        let longest = (0..units.len())
+            .filter(|&i| !units[i].is_empty() && rest.starts_with(units[i]))
+            .max_by_key(|&i| units[i].len());
+        match longest {
+            Some(i) => {
+                tokens.push(i);
+                rest = &rest[units[i].len()..];
+            }
+            None => rest = &rest[c.len_utf8()..],
+        }
+    }
+    tokens
+}
+
 pub fn refill(input: &str, template: &str, alphabet: &str) -> String {
     let mut output = String::with_capacity(template.len());
     let (input, mut i) = (input.to_string(), 0);
@@ -61,3 +79,31 @@ pub fn alphabetize(indices: Vec<u32>, alphabet: &str) -> String {
         .filter_map(|&i| alphabet.chars().nth(i as usize))
         .collect()
 }
+
+pub fn keyed_alphabet(keyword: &str, alphabet: &str) -> String {
+    let mut output = String::with_capacity(alphabet.len());
+    for c in keyword.chars().chain(alphabet.chars()) {
+        if alphabet.contains(c) && !output.contains(c) {
+            output.push(c);
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keyed_kryptos() {
+        assert_eq!(keyed_alphabet("KRYPTOS", ENGLISH), KRYPTOS);
+        assert_eq!(keyed_alphabet("PALIMPSEST", ENGLISH)[..8], *"PALIMSET");
+    }
+
+    #[test]
+    fn tokenize_longest() {
+        let units = ["A", "AN", "AND", "D", "T", "THE"];
+        assert_eq!(tokenize("THE AND, AT", &units), [5, 2, 0, 4]);
+        assert_eq!(tokenize("ANT", &units), [1, 4]);
+    }
+}
@@ -0,0 +1,224 @@
+use crate::common::{check_unique, filter, refill, Cipher, ENGLISH};
+
+pub const M94: [&str; 25] = [
+    "ABCEIGDJFVUYMHTQKZOLRXSPWN",
+    "ACDEHFIJKTLMOUVYGZNPQXRWSB",
+    "ADKOMJUBGEPHSCZINXFYQRTVWL",
+    "AEDCBIFGJHLKMRUOQVPTNWYXZS",
+    "AFNQUKDOPITJBRHCYSLWEMZVXG",
+    "AGPOCIXLURNDYZHWBJSQFKVMET",
+    "AHXJEZBNIKPVROGSYDULCFMQTW",
+    "AIHPJOBWKCVFZLQERYNSUMGTDX",
+    "AJDSKQOIVTZEFHGYUNLPMBXWCR",
+    "AKELBDFJGHONMTPRQSVZUXYWIC",
+    "ALTMSXVQPNOHUWDIZYCGKRFBEJ",
+    "AMNFLHQGCUJTBYPZKXISRDVEWO",
+    "ANCJILDHBMKGXUZTSWQYVORPFE",
+    "AODWPKJVIUQHZCTXBLEGNYRSMF",
+    "APBVHIYKSGUENTCXOWFQDRLJZM",
+    "AQJNUBTGIMWZRVLXCSHDEOKFPY",
+    "ARMYOFTHEUSZJXDPCWGQIBKLNV",
+    "ASDMCNEQBOZPLGVJRKYTFUIWXH",
+    "ATOJYLFXNGWHVCMIRBSEKUPDZQ",
+    "AUTRZXQLYIOVBPESNHJWMDGFCK",
+    "AVNKHRGOXEYBFSJMUDQCLZWTIP",
+    "AWVSFDLIEBHKNRJQZGMXPUCOTY",
+    "AXKWREVDTUFOYHMLSIQNJCPGBZ",
+    "AYJPXMVKBQWUGLOSTECHNZFRID",
+    "AZDNBUHYFWJLVGRCQMPSOEXTKI",
+];
+
+// generatrix offset and disk order, `None` where the crib does not reach
+pub type Placement = (usize, Vec<Option<usize>>);
+
+fn check_disks(alphabet: &str, disks: &[&str]) -> Result<Vec<Vec<char>>, String> {
+    let alphalen = alphabet.chars().count();
+    disks
+        .iter()
+        .map(|disk| {
+            let chars: Vec<char> = disk.chars().collect();
+            match chars.len() == alphalen && alphabet.chars().all(|c| chars.contains(&c)) {
+                true => Ok(chars),
+                false => Err(format!(
+                    "Disk {} is not a permutation of the alphabet!",
+                    disk
+                )),
+            }
+        })
+        .collect()
+}
+
+#[derive(Debug)]
+pub struct Cylinder {
+    alphabet: String,
+    disks: Vec<Vec<char>>,
+    order: Vec<usize>,
+    offset: usize,
+}
+impl Cylinder {
+    // disks are numbered from 1
+    pub fn new(
+        alphabet: &str,
+        disks: &[&str],
+        order: &[usize],
+        offset: usize,
+    ) -> Result<Self, String> {
+        check_unique(alphabet).unwrap();
+        let disks = check_disks(alphabet, disks)?;
+        if order.is_empty()
+            || order.iter().any(|&d| d == 0 || d > disks.len())
+            || (1..order.len()).any(|i| order[..i].contains(&order[i]))
+        {
+            return Err(String::from("Order must use distinct disks from the set!"));
+        }
+        let alphalen = alphabet.chars().count();
+        if offset == 0 || offset >= alphalen {
+            return Err(format!("Offset must be in 1..{}!", alphalen));
+        }
+        Ok(Self {
+            alphabet: alphabet.to_string(),
+            disks,
+            order: order.iter().map(|d| d - 1).collect(),
+            offset,
+        })
+    }
+    pub fn new_m94(order: &[usize], offset: usize) -> Result<Self, String> {
+        Self::new(ENGLISH, &M94, order, offset)
+    }
+
+    pub fn bazeries(
+        alphabet: &str,
+        disks: &[&str],
+        width: usize,
+        crib: &str,
+        ciphertxt: &str,
+        limit: usize,
+    ) -> Result<Vec<Placement>, String> {
+        let disks = check_disks(alphabet, disks)?;
+        if width == 0 || width > disks.len() {
+            return Err(String::from("Width must not exceed the number of disks!"));
+        }
+        let pairs: Vec<(char, char)> = filter(crib, alphabet)
+            .chars()
+            .zip(filter(ciphertxt, alphabet).chars())
+            .collect();
+        let alphalen = alphabet.chars().count();
+
+        let mut solutions = Vec::new();
+        for offset in 1..alphalen {
+            let fits = |disk: &[char], (p, c): (char, char)| {
+                let i = disk.iter().position(|&x| x == p).unwrap();
+                disk[(i + offset) % alphalen] == c
+            };
+            let mut candidates: Vec<(usize, Vec<usize>)> = (0..width.min(pairs.len()))
+                .map(|pos| {
+                    let column: Vec<(char, char)> =
+                        pairs.iter().skip(pos).step_by(width).copied().collect();
+                    let fitting = (0..disks.len())
+                        .filter(|&d| column.iter().all(|&pair| fits(&disks[d], pair)));
+                    (pos, fitting.collect())
+                })
+                .collect();
+            // try the most constrained positions first
+            candidates.sort_by_key(|(_, fitting)| fitting.len());
+
+            let mut order = vec![None; width];
+            let mut found = Vec::new();
+            assign(&candidates, &mut order, &mut found, limit - solutions.len());
+            solutions.extend(found.into_iter().map(|order| (offset, order)));
+            if solutions.len() >= limit {
+                break;
+            }
+        }
+        Ok(solutions)
+    }
+
+    fn substitute(&self, input: &str, decrypt: bool) -> String {
+        let alphalen = self.alphabet.chars().count();
+        let shift = match decrypt {
+            false => self.offset,
+            true => alphalen - self.offset,
+        };
+        let output: String = filter(input, &self.alphabet)
+            .chars()
+            .enumerate()
+            .map(|(i, c)| {
+                let disk = &self.disks[self.order[i % self.order.len()]];
+                let at = disk.iter().position(|&x| x == c).unwrap();
+                disk[(at + shift) % alphalen]
+            })
+            .collect();
+        refill(&output, input, &self.alphabet)
+    }
+}
+impl Cipher for Cylinder {
+    fn encrypt(&self, plaintext: &str) -> String {
+        self.substitute(plaintext, false)
+    }
+    fn decrypt(&self, ciphertxt: &str) -> String {
+        self.substitute(ciphertxt, true)
+    }
+}
+
+fn assign(
+    candidates: &[(usize, Vec<usize>)],
+    order: &mut Vec<Option<usize>>,
+    found: &mut Vec<Vec<Option<usize>>>,
+    limit: usize,
+) {
+    let Some(((pos, fitting), rest)) = candidates.split_first() else {
+        found.push(order.iter().map(|d| d.map(|d| d + 1)).collect());
+        return;
+    };
+    for &disk in fitting {
+        if found.len() >= limit {
+            return;
+        }
+        if order.contains(&Some(disk)) {
+            continue;
+        }
+        order[*pos] = Some(disk);
+        assign(rest, order, found, limit);
+        order[*pos] = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ORDER: [usize; 25] = [
+        7, 9, 5, 10, 1, 6, 3, 8, 2, 4, 13, 25, 22, 18, 15, 20, 12, 19, 16, 11, 23, 14, 24, 21, 17,
+    ];
+
+    #[test]
+    fn m94() {
+        let cylinder = Cylinder::new_m94(&[17, 1, 2], 1).unwrap();
+        assert_eq!(cylinder.encrypt("ARM"), "RXO");
+        assert_eq!(cylinder.decrypt("RXO"), "ARM");
+
+        let plaintext = include_str!("kryptos/k3_plain.txt").trim();
+        let cylinder = Cylinder::new_m94(&ORDER, 11).unwrap();
+        assert_eq!(cylinder.decrypt(&cylinder.encrypt(plaintext)), plaintext);
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(Cylinder::new_m94(&[1, 2, 2], 1).is_err());
+        assert!(Cylinder::new_m94(&[26], 1).is_err());
+        assert!(Cylinder::new_m94(&[1, 2], 26).is_err());
+        assert!(Cylinder::new(ENGLISH, &["ABC"], &[1], 1).is_err());
+    }
+
+    #[test]
+    fn bazeries() {
+        let plaintext = filter(include_str!("kryptos/k3_plain.txt"), ENGLISH);
+        let cylinder = Cylinder::new_m94(&ORDER, 11).unwrap();
+        let ciphertxt = cylinder.encrypt(&plaintext);
+        let solutions = Cylinder::bazeries(ENGLISH, &M94, 25, &plaintext[..50], &ciphertxt, 10);
+        let solutions = solutions.unwrap();
+        assert!(solutions.len() < 10);
+        let order: Vec<Option<usize>> = ORDER.iter().map(|&d| Some(d)).collect();
+        assert!(solutions.contains(&(11, order)));
+    }
+}
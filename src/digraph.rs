@@ -0,0 +1,235 @@
+use crate::common::{char_index, check_unique, filter, refill, Cipher};
+use crate::polybius::{merge_ij, Square};
+
+fn digraphs(input: &str, alphabet: &str, null: char) -> Vec<(char, char)> {
+    let mut clean: Vec<char> = filter(&merge_ij(input, alphabet), alphabet)
+        .chars()
+        .collect();
+    if clean.len() % 2 == 1 {
+        clean.push(null);
+    }
+    clean.chunks(2).map(|pair| (pair[0], pair[1])).collect()
+}
+
+#[derive(Debug)]
+pub struct FourSquare {
+    plain: Square,
+    upper: Square,
+    lower: Square,
+    null: char,
+}
+impl FourSquare {
+    pub fn new(alphabet: &str, key1: &str, key2: &str, null: char) -> Result<Self, String> {
+        if !alphabet.contains(null) {
+            return Err(String::from("Null character not in alphabet!"));
+        }
+        Ok(Self {
+            plain: Square::new(alphabet, "")?,
+            upper: Square::new(alphabet, key1)?,
+            lower: Square::new(alphabet, key2)?,
+            null,
+        })
+    }
+
+    fn substitute(&self, input: &str, decrypt: bool) -> String {
+        let alphabet = self.plain.alphabet();
+        let (first, second) = match decrypt {
+            false => (&self.plain, &self.plain),
+            true => (&self.upper, &self.lower),
+        };
+        let (third, fourth) = match decrypt {
+            false => (&self.upper, &self.lower),
+            true => (&self.plain, &self.plain),
+        };
+        let mut output = String::with_capacity(input.len() + 1);
+        for (a, b) in digraphs(input, alphabet, self.null) {
+            let (r1, c1) = first.locate(a).unwrap();
+            let (r2, c2) = second.locate(b).unwrap();
+            output.push(third.at(r1, c2));
+            output.push(fourth.at(r2, c1));
+        }
+        refill(&output, &merge_ij(input, alphabet), alphabet)
+    }
+}
+impl Cipher for FourSquare {
+    fn encrypt(&self, plaintext: &str) -> String {
+        self.substitute(plaintext, false)
+    }
+    fn decrypt(&self, ciphertxt: &str) -> String {
+        self.substitute(ciphertxt, true)
+    }
+}
+
+#[derive(Debug)]
+pub struct TwoSquare {
+    first: Square,
+    second: Square,
+    vertical: bool,
+    null: char,
+}
+impl TwoSquare {
+    pub fn new(alphabet: &str, key1: &str, key2: &str, null: char) -> Result<Self, String> {
+        if !alphabet.contains(null) {
+            return Err(String::from("Null character not in alphabet!"));
+        }
+        Ok(Self {
+            first: Square::new(alphabet, key1)?,
+            second: Square::new(alphabet, key2)?,
+            vertical: false,
+            null,
+        })
+    }
+    pub fn new_vertical(
+        alphabet: &str,
+        key1: &str,
+        key2: &str,
+        null: char,
+    ) -> Result<Self, String> {
+        Ok(Self {
+            vertical: true,
+            ..Self::new(alphabet, key1, key2, null)?
+        })
+    }
+
+    fn substitute(&self, input: &str) -> String {
+        let alphabet = self.first.alphabet();
+        let mut output = String::with_capacity(input.len() + 1);
+        for (a, b) in digraphs(input, alphabet, self.null) {
+            let (r1, c1) = self.first.locate(a).unwrap();
+            let (r2, c2) = self.second.locate(b).unwrap();
+            // letters sharing a row (or column, if vertical) stay transparent
+            match self.vertical {
+                false => output.extend([self.first.at(r2, c1), self.second.at(r1, c2)]),
+                true => output.extend([self.first.at(r1, c2), self.second.at(r2, c1)]),
+            }
+        }
+        refill(&output, &merge_ij(input, alphabet), alphabet)
+    }
+}
+impl Cipher for TwoSquare {
+    fn encrypt(&self, plaintext: &str) -> String {
+        self.substitute(plaintext)
+    }
+    fn decrypt(&self, ciphertxt: &str) -> String {
+        self.substitute(ciphertxt)
+    }
+}
+
+#[derive(Debug)]
+pub struct Slidefair {
+    alphabet: String,
+    shifts: Vec<usize>,
+    null: char,
+}
+impl Slidefair {
+    pub fn new(alphabet: &str, keyword: &str, null: char) -> Result<Self, String> {
+        check_unique(alphabet).unwrap();
+        if keyword.is_empty() || filter(keyword, alphabet) != keyword {
+            return Err(String::from("Keyword contains invalid characters!"));
+        }
+        if !alphabet.contains(null) {
+            return Err(String::from("Null character not in alphabet!"));
+        }
+        Ok(Self {
+            alphabet: alphabet.to_string(),
+            shifts: char_index(keyword, alphabet)
+                .into_iter()
+                .map(|k| k as usize)
+                .collect(),
+            null,
+        })
+    }
+
+    fn substitute(&self, input: &str, decrypt: bool) -> String {
+        let chars: Vec<char> = self.alphabet.chars().collect();
+        let n = chars.len();
+        let index = |c: char| chars.iter().position(|&x| x == c).unwrap();
+        let mut output = String::with_capacity(input.len() + 1);
+        for (i, (a, b)) in digraphs(input, &self.alphabet, self.null)
+            .into_iter()
+            .enumerate()
+        {
+            let k = self.shifts[i % self.shifts.len()];
+            let (a, b) = (index(a), index(b));
+            let (c, d) = match (b == (a + k) % n, decrypt) {
+                (true, false) => ((a + 1) % n, (a + 1 + k) % n),
+                (true, true) => ((a + n - 1) % n, (a + n - 1 + k) % n),
+                (false, _) => ((b + n - k) % n, (a + k) % n),
+            };
+            output.extend([chars[c], chars[d]]);
+        }
+        refill(&output, input, &self.alphabet)
+    }
+}
+impl Cipher for Slidefair {
+    fn encrypt(&self, plaintext: &str) -> String {
+        self.substitute(plaintext, false)
+    }
+    fn decrypt(&self, ciphertxt: &str) -> String {
+        self.substitute(ciphertxt, true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::ENGLISH;
+    use crate::polybius::POLYBIUS;
+
+    const PLAINTEXT: &str = "HELP ME OBI WAN KENOBI";
+
+    #[test]
+    fn four_square() {
+        let ciphertxt = "FYGM KY HOB XMF KKKIMD";
+        let no_q = ENGLISH.replace('Q', "");
+        let four = FourSquare::new(&no_q, "EXAMPLE", "KEYWORD", 'X').unwrap();
+        assert_eq!(four.encrypt(PLAINTEXT), ciphertxt);
+        assert_eq!(four.decrypt(ciphertxt), PLAINTEXT);
+    }
+
+    #[test]
+    fn four_square_null() {
+        let four = FourSquare::new(POLYBIUS, "EXAMPLE", "KEYWORD", 'Q').unwrap();
+        let ciphertxt = four.encrypt("JOY");
+        assert_eq!(ciphertxt.len(), 4);
+        assert_eq!(four.decrypt(&ciphertxt), "IOYQ");
+        assert!(FourSquare::new(POLYBIUS, "EXAMPLE", "KEYWORD", 'J').is_err());
+    }
+
+    #[test]
+    fn two_square() {
+        let no_q = ENGLISH.replace('Q', "");
+        let horizontal = TwoSquare::new(&no_q, "EXAMPLE", "KEYWORD", 'X').unwrap();
+        let ciphertxt = horizontal.encrypt(PLAINTEXT);
+        assert_eq!(ciphertxt, "XGNB ME BPA IRY PGESHB");
+        assert_eq!(horizontal.decrypt(&ciphertxt), PLAINTEXT);
+
+        let ciphertxt = "HEDL XW SDJ YAN HOTKDG";
+        let vertical = TwoSquare::new_vertical(&no_q, "EXAMPLE", "KEYWORD", 'X').unwrap();
+        assert_eq!(vertical.encrypt(PLAINTEXT), ciphertxt);
+        assert_eq!(vertical.decrypt(ciphertxt), PLAINTEXT);
+    }
+
+    #[test]
+    fn two_square_polybius() {
+        let two = TwoSquare::new(POLYBIUS, "JUPITER", "SATURN", 'X').unwrap();
+        let ciphertxt = two.encrypt("JOVIAN");
+        assert_eq!(two.decrypt(&ciphertxt), "IOVIAN");
+    }
+
+    #[test]
+    fn slidefair() {
+        let slidefair = Slidefair::new(ENGLISH, "D", 'X').unwrap();
+        assert_eq!(slidefair.encrypt("AD AB"), "BE YD");
+        assert_eq!(slidefair.decrypt("BE YD"), "AD AB");
+        // apart from pairs in one column, the cipher is its own inverse
+        assert_eq!(slidefair.encrypt("YD"), "AB");
+
+        let slidefair = Slidefair::new(ENGLISH, "DIGRAPH", 'X').unwrap();
+        let ciphertxt = "BKHT YS KFW IYP XRLQAJ";
+        assert_eq!(slidefair.encrypt(PLAINTEXT), ciphertxt);
+        assert_eq!(slidefair.decrypt(ciphertxt), PLAINTEXT);
+        assert_eq!(slidefair.decrypt(&slidefair.encrypt("ODD")), "ODDX");
+        assert!(Slidefair::new(ENGLISH, "DIGRAPH", '*').is_err());
+    }
+}
@@ -0,0 +1,128 @@
+use crate::common::{Cipher, ENGLISH};
+use crate::rotor::{DoubleStep, Machine, Plugboard, Reflector, Rotor, Stator};
+
+pub const ROTORS: [(&str, &str); 5] = [
+    ("EKMFLGDQVZNTOWYHXUSPAIBRCJ", "Q"),
+    ("AJDKSIRUXBLHWTMCQGZNPYFVOE", "E"),
+    ("BDFHJLCPRTXVZNYEIWGAKMUSQO", "V"),
+    ("ESOVPZJAYQUIRHXLNFTGKDCMWB", "J"),
+    ("VZBRGITYUPSDNHLXAWMJQOFECK", "Z"),
+];
+pub const NAMES: [&str; 5] = ["I", "II", "III", "IV", "V"];
+pub const REFLECTORS: [(&str, &str); 2] = [
+    ("B", "YRUHQSLDPXNGOKMIEBFZCWVJAT"),
+    ("C", "FVPJIAOYEDRZXWGCTKUQSBNMHL"),
+];
+
+pub fn parse_order(order: &str) -> Result<[usize; 3], String> {
+    let order = order
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|s| !s.is_empty())
+        .map(|name| NAMES.iter().position(|&n| n == name))
+        .collect::<Option<Vec<usize>>>()
+        .ok_or(String::from("Rotor order contains unknown rotors!"))?;
+    match order[..] {
+        [a, b, c] if a != b && b != c && a != c => Ok([a, b, c]),
+        _ => Err(String::from("Order must pick 3 distinct rotors!")),
+    }
+}
+pub fn order_name(order: &[usize; 3]) -> String {
+    order.map(|r| NAMES[r]).join(" ")
+}
+
+pub fn reflector(name: &str) -> Result<Reflector, String> {
+    let (_, wiring) = REFLECTORS
+        .iter()
+        .find(|(n, _)| *n == name)
+        .ok_or(format!("Unknown reflector: {}", name))?;
+    Reflector::new(ENGLISH, wiring)
+}
+
+pub fn rotors(order: &[usize; 3], rings: &str, positions: &str) -> Result<Vec<Rotor>, String> {
+    let letters = |s: &str| -> Result<Vec<usize>, String> {
+        let indices: Option<Vec<usize>> = s.chars().map(|c| ENGLISH.find(c)).collect();
+        match indices {
+            Some(indices) if indices.len() == 3 => Ok(indices),
+            _ => Err(format!("Expected 3 letters: {}", s)),
+        }
+    };
+    let (rings, positions) = (letters(rings)?, letters(positions)?);
+    order
+        .iter()
+        .enumerate()
+        .map(|(i, &r)| {
+            let (wiring, notch) = ROTORS[r];
+            let mut rotor = Rotor::new(ENGLISH, wiring, notch)?;
+            rotor.set_ring(rings[i]);
+            rotor.set_position(positions[i]);
+            Ok(rotor)
+        })
+        .collect()
+}
+
+#[derive(Debug)]
+pub struct Enigma {
+    machine: Machine,
+}
+impl Enigma {
+    pub fn new(
+        order: &str,
+        reflector: &str,
+        rings: &str,
+        positions: &str,
+        plugboard: &str,
+    ) -> Result<Self, String> {
+        let order = parse_order(order)?;
+        let machine = Machine::new(
+            ENGLISH,
+            Plugboard::new(ENGLISH, plugboard)?,
+            Stator::identity(ENGLISH.len()),
+            rotors(&order, rings, positions)?,
+            Vec::new(),
+            Some(self::reflector(reflector)?),
+            Box::new(DoubleStep),
+        )?;
+        Ok(Self { machine })
+    }
+
+    pub fn positions(&self) -> String {
+        self.machine.positions()
+    }
+    pub fn set_positions(&mut self, positions: &str) -> Result<(), String> {
+        self.machine.set_positions(positions)
+    }
+}
+impl Cipher for Enigma {
+    fn encrypt(&self, plaintext: &str) -> String {
+        self.machine.encrypt(plaintext)
+    }
+    fn decrypt(&self, ciphertxt: &str) -> String {
+        self.machine.decrypt(ciphertxt)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn enigma_i() {
+        let enigma = Enigma::new("I II III", "B", "AAA", "AAA", "").unwrap();
+        assert_eq!(enigma.encrypt("AAAAA"), "BDZGO");
+        let enigma = Enigma::new("I II III", "B", "BBB", "AAA", "").unwrap();
+        assert_eq!(enigma.encrypt("AAAAA"), "EWTYX");
+
+        let enigma = Enigma::new("IV I V", "C", "GMY", "DHO", "AQ BW CE DR").unwrap();
+        let ciphertxt = enigma.encrypt("NOTHING TO REPORT");
+        assert_eq!(enigma.decrypt(&ciphertxt), "NOTHING TO REPORT");
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(parse_order("I I III").is_err());
+        assert!(parse_order("I II VI").is_err());
+        assert!(Enigma::new("I II III", "A", "AAA", "AAA", "").is_err());
+        assert!(Enigma::new("I II III", "B", "AAA", "AAA", "AB AC").is_err());
+        assert_eq!(order_name(&parse_order("V, I, II").unwrap()), "V I II");
+    }
+}
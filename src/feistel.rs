@@ -0,0 +1,228 @@
+use crate::common::{alphabetize, char_index, check_unique, filter, refill, Cipher};
+use std::str::FromStr;
+
+pub type RoundFn = fn(half: &[u32], key: &[u32], modulus: u32) -> Vec<u32>;
+
+pub fn additive(half: &[u32], key: &[u32], modulus: u32) -> Vec<u32> {
+    half.iter()
+        .zip(key.iter())
+        .map(|(h, k)| (h + k) % modulus)
+        .collect()
+}
+
+pub fn mixing(half: &[u32], key: &[u32], modulus: u32) -> Vec<u32> {
+    let n = half.len();
+    (0..n)
+        .map(|j| {
+            let a = half[j] + key[j];
+            let b = half[(j + 1) % n] + key[(j + n - 1) % n];
+            (a * a + 3 * b + j as u32) % modulus
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Mode {
+    Ecb,
+    Cbc,
+    Ctr,
+}
+impl FromStr for Mode {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "ecb" => Ok(Mode::Ecb),
+            "cbc" => Ok(Mode::Cbc),
+            "ctr" => Ok(Mode::Ctr),
+            _ => Err(format!("Unknown block mode: {}", s)),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Feistel {
+    alphabet: String,
+    alphalen: u32,
+    block_size: usize,
+    round_keys: Vec<Vec<u32>>,
+    round: RoundFn,
+    mode: Mode,
+    iv: Vec<u32>,
+}
+impl Feistel {
+    pub fn new(
+        alphabet: &str,
+        keyword: &str,
+        n_rounds: usize,
+        block_size: usize,
+        mode: Mode,
+    ) -> Result<Self, String> {
+        Self::with_round(alphabet, keyword, n_rounds, block_size, mode, mixing)
+    }
+    pub fn with_round(
+        alphabet: &str,
+        keyword: &str,
+        n_rounds: usize,
+        block_size: usize,
+        mode: Mode,
+        round: RoundFn,
+    ) -> Result<Self, String> {
+        check_unique(alphabet).unwrap();
+        if keyword.is_empty() || !keyword.chars().all(|c| alphabet.contains(c)) {
+            return Err(String::from("Keyword contains invalid characters!"));
+        }
+        if block_size < 2 || !block_size.is_multiple_of(2) {
+            return Err(String::from("Block size must be a positive even number!"));
+        }
+        let alphalen = alphabet.chars().count() as u32;
+        let kw_idx = char_index(keyword, alphabet);
+        let round_keys = (0..n_rounds)
+            .map(|i| {
+                (0..block_size / 2)
+                    .map(|j| (kw_idx[(i + j) % kw_idx.len()] + i as u32) % alphalen)
+                    .collect()
+            })
+            .collect();
+        Ok(Self {
+            alphabet: alphabet.to_string(),
+            alphalen,
+            block_size,
+            round_keys,
+            round,
+            mode,
+            iv: vec![0; block_size],
+        })
+    }
+    pub fn set_iv(&mut self, iv: &str) -> Result<(), String> {
+        let iv = char_index(iv, &self.alphabet);
+        if iv.len() != self.block_size {
+            return Err(String::from("IV must fill exactly one block!"));
+        }
+        self.iv = iv;
+        Ok(())
+    }
+
+    fn combine(&self, a: &[u32], b: &[u32], subtract: bool) -> Vec<u32> {
+        let n = self.alphalen;
+        a.iter()
+            .zip(b.iter())
+            .map(|(x, y)| match subtract {
+                false => (x + y) % n,
+                true => (x + n - y) % n,
+            })
+            .collect()
+    }
+
+    fn encrypt_block(&self, block: &[u32]) -> Vec<u32> {
+        let (left, right) = block.split_at(self.block_size / 2);
+        let (mut l, mut r) = (left.to_vec(), right.to_vec());
+        for key in self.round_keys.iter() {
+            let f = (self.round)(&r, key, self.alphalen);
+            (l, r) = (r, self.combine(&l, &f, false));
+        }
+        [l, r].concat()
+    }
+    fn decrypt_block(&self, block: &[u32]) -> Vec<u32> {
+        let (left, right) = block.split_at(self.block_size / 2);
+        let (mut l, mut r) = (left.to_vec(), right.to_vec());
+        for key in self.round_keys.iter().rev() {
+            let f = (self.round)(&l, key, self.alphalen);
+            (l, r) = (self.combine(&r, &f, true), l);
+        }
+        [l, r].concat()
+    }
+    fn counter_block(&self, counter: usize) -> Vec<u32> {
+        let mut block = self.iv.clone();
+        let mut carry = counter;
+        for digit in block.iter_mut().rev() {
+            let sum = *digit as usize + carry;
+            *digit = (sum % self.alphalen as usize) as u32;
+            carry = sum / self.alphalen as usize;
+        }
+        block
+    }
+
+    fn process(&self, input: &str, decrypt: bool) -> String {
+        let mut indices = char_index(&filter(input, &self.alphabet), &self.alphabet);
+        let n_chars = indices.len();
+        if self.mode != Mode::Ctr {
+            let n_pad = (self.block_size - n_chars % self.block_size) % self.block_size;
+            indices.resize(n_chars + n_pad, self.alphalen - 1);
+        }
+
+        let mut output = Vec::with_capacity(indices.len());
+        let mut chain = self.iv.clone();
+        for (i, block) in indices.chunks(self.block_size).enumerate() {
+            let processed = match (self.mode, decrypt) {
+                (Mode::Ecb, false) => self.encrypt_block(block),
+                (Mode::Ecb, true) => self.decrypt_block(block),
+                (Mode::Cbc, false) => {
+                    chain = self.encrypt_block(&self.combine(block, &chain, false));
+                    chain.clone()
+                }
+                (Mode::Cbc, true) => {
+                    let plain = self.combine(&self.decrypt_block(block), &chain, true);
+                    chain = block.to_vec();
+                    plain
+                }
+                (Mode::Ctr, _) => {
+                    let keystream = self.encrypt_block(&self.counter_block(i));
+                    self.combine(block, &keystream[..block.len()], decrypt)
+                }
+            };
+            output.extend(processed);
+        }
+        refill(&alphabetize(output, &self.alphabet), input, &self.alphabet)
+    }
+}
+impl Cipher for Feistel {
+    fn encrypt(&self, plaintext: &str) -> String {
+        self.process(plaintext, false)
+    }
+    fn decrypt(&self, ciphertxt: &str) -> String {
+        self.process(ciphertxt, true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::ENGLISH;
+
+    const PLAINTEXT: &str = "ATTACK AT DAWN ATTACK AT DAWN";
+
+    #[test]
+    fn ecb() {
+        let feistel = Feistel::new(ENGLISH, "ROUNDKEY", 8, 6, Mode::Ecb).unwrap();
+        let ciphertxt = feistel.encrypt(PLAINTEXT);
+        let clean = filter(&ciphertxt, ENGLISH);
+        assert_eq!(clean[..6], clean[12..18]);
+        assert_eq!(feistel.decrypt(&ciphertxt)[..PLAINTEXT.len()], *PLAINTEXT);
+    }
+
+    #[test]
+    fn cbc() {
+        let mut feistel = Feistel::new(ENGLISH, "ROUNDKEY", 8, 6, Mode::Cbc).unwrap();
+        feistel.set_iv("VECTOR").unwrap();
+        let ciphertxt = feistel.encrypt(PLAINTEXT);
+        let clean = filter(&ciphertxt, ENGLISH);
+        assert_ne!(clean[..6], clean[12..18]);
+        assert_eq!(feistel.decrypt(&ciphertxt)[..PLAINTEXT.len()], *PLAINTEXT);
+        assert!(feistel.set_iv("SHORT").is_err());
+    }
+
+    #[test]
+    fn ctr() {
+        let feistel = Feistel::with_round(ENGLISH, "ROUNDKEY", 4, 4, Mode::Ctr, additive).unwrap();
+        let ciphertxt = feistel.encrypt(PLAINTEXT);
+        assert_eq!(ciphertxt.len(), PLAINTEXT.len());
+        assert_eq!(feistel.decrypt(&ciphertxt), PLAINTEXT);
+    }
+
+    #[test]
+    fn modes() {
+        assert_eq!("CBC".parse::<Mode>(), Ok(Mode::Cbc));
+        assert!("OFB".parse::<Mode>().is_err());
+        assert!(Feistel::new(ENGLISH, "ROUNDKEY", 8, 5, Mode::Ecb).is_err());
+    }
+}
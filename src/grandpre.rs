@@ -0,0 +1,132 @@
+use crate::common::{check_unique, filter, Cipher};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::HashMap;
+
+#[derive(Debug)]
+pub struct Grandpre {
+    alphabet: String,
+    rows: Vec<Vec<char>>,
+    seed: Option<u64>,
+    rotating: bool,
+}
+impl Grandpre {
+    pub fn new(alphabet: &str, square: &str, seed: Option<u64>) -> Result<Self, String> {
+        check_unique(alphabet).unwrap();
+        let rows: Vec<Vec<char>> = square
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|row| !row.is_empty())
+            .map(|row| row.chars().collect())
+            .collect();
+        let size = rows.len();
+        if (size != 8 && size != 10) || rows.iter().any(|row| row.len() != size) {
+            return Err(String::from("Square must be 8x8 or 10x10!"));
+        }
+        if !rows.iter().flatten().all(|c| alphabet.contains(*c)) {
+            return Err(String::from("Square contains invalid characters!"));
+        }
+        if !alphabet
+            .chars()
+            .all(|c| rows.iter().flatten().any(|x| *x == c))
+        {
+            return Err(String::from(
+                "Square must contain every character of the alphabet!",
+            ));
+        }
+        Ok(Self {
+            alphabet: alphabet.to_string(),
+            rows,
+            seed,
+            rotating: false,
+        })
+    }
+    pub fn new_rotating(alphabet: &str, square: &str) -> Result<Self, String> {
+        Ok(Self {
+            rotating: true,
+            ..Self::new(alphabet, square, None)?
+        })
+    }
+
+    fn digit(&self, idx: usize) -> char {
+        char::from_digit((idx as u32 + 1) % 10, 10).unwrap()
+    }
+    fn index(&self, digit: u32) -> usize {
+        (digit as usize + 9) % 10
+    }
+    fn homophones(&self, c: char) -> Vec<String> {
+        let mut coords = Vec::new();
+        for (i, row) in self.rows.iter().enumerate() {
+            for (j, _) in row.iter().enumerate().filter(|(_, x)| **x == c) {
+                coords.push(format!("{}{}", self.digit(i), self.digit(j)));
+            }
+        }
+        coords
+    }
+}
+impl Cipher for Grandpre {
+    fn encrypt(&self, plaintext: &str) -> String {
+        let mut rng = match self.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        let mut turns: HashMap<char, usize> = HashMap::new();
+        filter(plaintext, &self.alphabet)
+            .chars()
+            .map(|c| {
+                let coords = self.homophones(c);
+                let choice = match self.rotating {
+                    false => rng.gen_range(0..coords.len()),
+                    true => {
+                        let turn = turns.entry(c).or_insert(0);
+                        *turn += 1;
+                        (*turn - 1) % coords.len()
+                    }
+                };
+                coords[choice].clone()
+            })
+            .collect()
+    }
+    fn decrypt(&self, ciphertxt: &str) -> String {
+        let digits: Vec<u32> = ciphertxt.chars().filter_map(|c| c.to_digit(10)).collect();
+        digits
+            .chunks_exact(2)
+            .filter_map(|pair| {
+                let row = self.rows.get(self.index(pair[0]))?;
+                row.get(self.index(pair[1])).copied()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::ENGLISH;
+
+    const SQUARE: &str = "LADYBUGS AZIMUTHS CALFSKIN QUACKISH UNJOVIAL EVULSION ROWDYISM SEXTUPLY";
+    const PLAINTEXT: &str = "THEQUICKBROWNFOXJUMPSOVERTHELAZYDOG";
+
+    #[test]
+    fn rotating() {
+        let grandpre = Grandpre::new_rotating(ENGLISH, SQUARE).unwrap();
+        let ciphertxt = grandpre.encrypt("ASSESS");
+        assert_eq!(ciphertxt, "121828613547");
+        assert_eq!(grandpre.decrypt(&ciphertxt), "ASSESS");
+    }
+
+    #[test]
+    fn seeded() {
+        let grandpre = Grandpre::new(ENGLISH, SQUARE, Some(1917)).unwrap();
+        let ciphertxt = grandpre.encrypt(PLAINTEXT);
+        assert_eq!(ciphertxt.len(), 2 * PLAINTEXT.len());
+        assert_eq!(ciphertxt, grandpre.encrypt(PLAINTEXT));
+        assert_eq!(grandpre.decrypt(&ciphertxt), PLAINTEXT);
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(Grandpre::new(ENGLISH, "LADYBUGS AZIMUTHS", None).is_err());
+        let missing_x = SQUARE.replace("SEXTUPLY", "SEPTUPLY");
+        assert!(Grandpre::new(ENGLISH, &missing_x, None).is_err());
+    }
+}
@@ -0,0 +1,132 @@
+use crate::common::{alphabetize, char_index, check_unique, filter, refill, Cipher};
+use crate::transpose::{columnar_alphabet, key_digits};
+
+pub fn chain_digits(primer: &[u32], length: usize) -> Vec<u32> {
+    let mut digits = primer.to_vec();
+    let lag = primer.len();
+    while digits.len() < length {
+        let i = digits.len() - lag;
+        digits.push((digits[i] + digits[i + 1]) % 10);
+    }
+    digits.truncate(length);
+    digits
+}
+
+#[derive(Debug)]
+pub struct Gromark {
+    alphabet: String,
+    mixed: String,
+    keyword: String,
+    primer: Vec<u32>,
+    periodic: bool,
+}
+impl Gromark {
+    pub fn new(alphabet: &str, keyword: &str, primer: &str) -> Result<Self, String> {
+        check_unique(alphabet).unwrap();
+        if !keyword.chars().all(|c| alphabet.contains(c)) {
+            return Err(String::from("Keyword contains invalid characters!"));
+        }
+        let primer: Vec<u32> = match primer.chars().map(|c| c.to_digit(10)).collect() {
+            Some(digits) => digits,
+            None => return Err(String::from("Primer must only contain digits!")),
+        };
+        if primer.len() < 2 {
+            return Err(String::from("Primer must have at least two digits!"));
+        }
+        Ok(Self {
+            alphabet: alphabet.to_string(),
+            mixed: columnar_alphabet(keyword, alphabet),
+            keyword: keyword.to_string(),
+            primer,
+            periodic: false,
+        })
+    }
+    pub fn new_periodic(alphabet: &str, keyword: &str) -> Result<Self, String> {
+        let primer: String = key_digits(keyword, alphabet)
+            .iter()
+            .map(|d| d.to_string())
+            .collect();
+        Ok(Self {
+            periodic: true,
+            ..Self::new(alphabet, keyword, &primer)?
+        })
+    }
+
+    fn substitute(&self, input: &str, decrypt: bool) -> String {
+        let clean = filter(input, &self.alphabet);
+        let alphalen = self.alphabet.len() as u32;
+        let period = self.primer.len();
+        let digits = chain_digits(&self.primer, clean.len());
+        let offsets: Vec<u32> = match self.periodic {
+            false => vec![0],
+            true => char_index(&self.keyword, &self.mixed),
+        };
+
+        let (from, to) = match decrypt {
+            false => (&self.alphabet, &self.mixed),
+            true => (&self.mixed, &self.alphabet),
+        };
+        let indices = char_index(&clean, from)
+            .into_iter()
+            .enumerate()
+            .map(|(i, idx)| {
+                let offset = offsets[(i / period) % offsets.len()];
+                match decrypt {
+                    false => (idx + digits[i] + offset) % alphalen,
+                    true => (idx + 2 * alphalen - offset - digits[i] % alphalen) % alphalen,
+                }
+            })
+            .collect();
+        refill(&alphabetize(indices, to), input, &self.alphabet)
+    }
+}
+impl Cipher for Gromark {
+    fn encrypt(&self, plaintext: &str) -> String {
+        self.substitute(plaintext, false)
+    }
+    fn decrypt(&self, ciphertxt: &str) -> String {
+        self.substitute(ciphertxt, true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::ENGLISH;
+
+    const PLAINTEXT: &str = "THERE ARE UP TO TEN SUBSTITUTES PER LETTER";
+
+    #[test]
+    fn mixed_alphabet() {
+        let gromark = Gromark::new(ENGLISH, "ENIGMA", "23452").unwrap();
+        assert_eq!(gromark.mixed, "AJRXEBKSYGFPVIDOUMHQWNCLTZ");
+    }
+
+    #[test]
+    fn running_key() {
+        let primer = [2, 3, 4, 5, 2];
+        let digits = chain_digits(&primer, 15);
+        assert_eq!(digits, [2, 3, 4, 5, 2, 5, 7, 9, 7, 7, 2, 6, 6, 4, 9]);
+    }
+
+    #[test]
+    fn gromark() {
+        let ciphertxt = "NFYCK BTI JC NW ZYC ACJNAYNLQPW WST WPJQFL";
+        let gromark = Gromark::new(ENGLISH, "ENIGMA", "23452").unwrap();
+        assert_eq!(gromark.encrypt(PLAINTEXT), ciphertxt);
+        assert_eq!(gromark.decrypt(ciphertxt), PLAINTEXT);
+
+        // running key digits may exceed the size of a short alphabet
+        let gromark = Gromark::new("ABCD", "CAB", "98").unwrap();
+        assert_eq!(gromark.decrypt(&gromark.encrypt("DABCAB")), "DABCAB");
+    }
+
+    #[test]
+    fn periodic() {
+        let gromark = Gromark::new_periodic(ENGLISH, "ENIGMA").unwrap();
+        assert_eq!(gromark.primer, [2, 6, 4, 3, 5, 1]);
+        let ciphertxt = "ZMVTI BWZ CH WH DTB GVNKEAPBERF DCV PVRRGW";
+        assert_eq!(gromark.encrypt(PLAINTEXT), ciphertxt);
+        assert_eq!(gromark.decrypt(ciphertxt), PLAINTEXT);
+    }
+}
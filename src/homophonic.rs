@@ -0,0 +1,294 @@
+use crate::common::{char_index, check_unique, filter, keyed_alphabet, Cipher, ENGLISH};
+use crate::stats::{NGrams, Solution, LETTER_FREQ};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::HashMap;
+
+pub const UNSEEN: char = '.';
+
+fn parse_symbols(ciphertxt: &str) -> Vec<usize> {
+    let digits: Vec<u32> = ciphertxt.chars().filter_map(|c| c.to_digit(10)).collect();
+    digits
+        .chunks_exact(2)
+        .map(|pair| (pair[0] * 10 + pair[1]) as usize)
+        .collect()
+}
+
+#[derive(Debug)]
+pub struct Homophonic {
+    alphabet: String,
+    table: Vec<Option<u32>>,
+}
+impl Homophonic {
+    pub fn new(alphabet: &str, keyword: &str, n_symbols: usize) -> Result<Self, String> {
+        check_unique(alphabet).unwrap();
+        let alphalen = alphabet.chars().count();
+        if n_symbols < alphalen || n_symbols > 100 {
+            return Err(format!("Number of symbols must be in {}..=100!", alphalen));
+        }
+
+        // allot homophones in proportion to English letter frequency, at least one each
+        let weights: Vec<f64> = alphabet
+            .chars()
+            .map(|c| match ENGLISH.chars().position(|x| x == c) {
+                Some(i) => LETTER_FREQ[i],
+                None => 0.0,
+            })
+            .collect();
+        let total: f64 = weights.iter().sum::<f64>().max(f64::EPSILON);
+        let spare = (n_symbols - alphalen) as f64;
+        let mut counts: Vec<usize> = weights
+            .iter()
+            .map(|w| 1 + (spare * w / total) as usize)
+            .collect();
+        let mut by_remainder: Vec<usize> = (0..alphalen).collect();
+        by_remainder.sort_by(|&a, &b| {
+            let rem = |i: usize| (spare * weights[i] / total).fract();
+            rem(b).total_cmp(&rem(a))
+        });
+        for &i in by_remainder
+            .iter()
+            .cycle()
+            .take(n_symbols - counts.iter().sum::<usize>())
+        {
+            counts[i] += 1;
+        }
+
+        // deal the symbols in turn to the letters of the keyed alphabet that still need one
+        let keyed = char_index(&keyed_alphabet(keyword, alphabet), alphabet);
+        let mut table = vec![None; n_symbols];
+        let mut dealt = vec![0; alphalen];
+        let mut symbol = 0;
+        while symbol < n_symbols {
+            for &letter in &keyed {
+                if dealt[letter as usize] < counts[letter as usize] {
+                    dealt[letter as usize] += 1;
+                    table[symbol] = Some(letter);
+                    symbol += 1;
+                }
+            }
+        }
+        Ok(Self {
+            alphabet: alphabet.to_string(),
+            table,
+        })
+    }
+    pub fn from_key(alphabet: &str, key: &str) -> Result<Self, String> {
+        check_unique(alphabet).unwrap();
+        if key.chars().count() > 100 {
+            return Err(String::from("Key must not exceed 100 symbols!"));
+        }
+        let table = key
+            .chars()
+            .map(|c| match c {
+                UNSEEN => Ok(None),
+                _ => match alphabet.chars().position(|x| x == c) {
+                    Some(i) => Ok(Some(i as u32)),
+                    None => Err(String::from("Key contains invalid characters!")),
+                },
+            })
+            .collect::<Result<Vec<Option<u32>>, String>>()?;
+        Ok(Self {
+            alphabet: alphabet.to_string(),
+            table,
+        })
+    }
+
+    pub fn key(&self) -> String {
+        self.table
+            .iter()
+            .map(|t| match t {
+                Some(i) => self.alphabet.chars().nth(*i as usize).unwrap(),
+                None => UNSEEN,
+            })
+            .collect()
+    }
+
+    pub fn solve(
+        alphabet: &str,
+        ciphertxt: &str,
+        restarts: usize,
+        iterations: usize,
+        seed: Option<u64>,
+    ) -> Solution {
+        check_unique(alphabet).unwrap();
+        let mut rng = match seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        // trigrams are less prone than quadgrams to collapse into repeated common words
+        let trigrams = NGrams::english(3);
+        let symbols = parse_symbols(ciphertxt);
+        let mut seen: Vec<usize> = symbols.clone();
+        seen.sort_unstable();
+        seen.dedup();
+        if seen.is_empty() {
+            return Solution {
+                key: String::new(),
+                score: f64::NEG_INFINITY,
+                confidence: 0.0,
+            };
+        }
+        let n_symbols = seen.last().map_or(0, |s| s + 1);
+        let n = alphabet.chars().count();
+        // plaintext is scored as English, skipping letters the n-grams do not cover
+        let english: Vec<Option<u32>> = alphabet
+            .chars()
+            .map(|c| ENGLISH.find(c).map(|i| i as u32))
+            .collect();
+        let freq: Vec<f64> = english
+            .iter()
+            .map(|e| e.map_or(0.0, |e| LETTER_FREQ[e as usize]))
+            .collect();
+        let decipher = |key: &[u32]| -> Vec<u32> {
+            symbols
+                .iter()
+                .filter_map(|&s| english[key[s] as usize])
+                .collect()
+        };
+        // penalize letter frequencies drifting away from English
+        let fitness = |plain: &[u32]| -> f64 {
+            let mut counts = [0.0; 26];
+            plain.iter().for_each(|&i| counts[i as usize] += 1.0);
+            let total = plain.len() as f64;
+            let chi: f64 = counts
+                .iter()
+                .zip(LETTER_FREQ.iter())
+                .map(|(&c, &f)| (c - total * f).powi(2) / (total * f))
+                .sum();
+            trigrams.score_indices(plain) - 0.5 * chi
+        };
+        let random_letter = |rng: &mut StdRng| -> u32 {
+            let mut target = rng.gen::<f64>() * freq.iter().sum::<f64>();
+            for (i, f) in freq.iter().enumerate() {
+                target -= f;
+                if target <= 0.0 {
+                    return i as u32;
+                }
+            }
+            n as u32 - 1
+        };
+
+        let (mut best_key, mut best_score) = (vec![0; n_symbols], f64::NEG_INFINITY);
+        for _ in 0..restarts.max(1) {
+            let mut key: Vec<u32> = (0..n_symbols).map(|_| random_letter(&mut rng)).collect();
+            let mut score = fitness(&decipher(&key));
+            for step in 0..iterations {
+                let temperature = 2.0 * (1.0 - step as f64 / iterations as f64);
+                let symbol = seen[rng.gen_range(0..seen.len())];
+                let previous = key[symbol];
+                key[symbol] = match rng.gen_bool(0.5) {
+                    false => rng.gen_range(0..n as u32),
+                    true => key[seen[rng.gen_range(0..seen.len())]],
+                };
+                let candidate = fitness(&decipher(&key));
+                let delta = candidate - score;
+                if delta >= 0.0 || rng.gen::<f64>() < (delta / temperature.max(1e-9)).exp() {
+                    score = candidate;
+                } else {
+                    key[symbol] = previous;
+                }
+            }
+            if score > best_score {
+                (best_key, best_score) = (key, score);
+            }
+        }
+
+        let key = (0..n_symbols)
+            .map(|s| match seen.binary_search(&s) {
+                Ok(_) => alphabet.chars().nth(best_key[s] as usize).unwrap(),
+                Err(_) => UNSEEN,
+            })
+            .collect();
+        Solution {
+            key,
+            score: best_score,
+            confidence: NGrams::english(4).confidence(&decipher(&best_key)),
+        }
+    }
+}
+impl Cipher for Homophonic {
+    fn encrypt(&self, plaintext: &str) -> String {
+        let mut homophones: Vec<Vec<usize>> = vec![Vec::new(); self.alphabet.chars().count()];
+        for (symbol, letter) in self.table.iter().enumerate() {
+            if let Some(letter) = letter {
+                homophones[*letter as usize].push(symbol);
+            }
+        }
+        let mut turns: HashMap<u32, usize> = HashMap::new();
+        char_index(&filter(plaintext, &self.alphabet), &self.alphabet)
+            .into_iter()
+            .filter(|&i| !homophones[i as usize].is_empty())
+            .map(|i| {
+                let choices = &homophones[i as usize];
+                let turn = turns.entry(i).or_insert(0);
+                *turn += 1;
+                format!("{:02}", choices[(*turn - 1) % choices.len()])
+            })
+            .collect()
+    }
+    fn decrypt(&self, ciphertxt: &str) -> String {
+        parse_symbols(ciphertxt)
+            .into_iter()
+            .filter_map(|s| self.table.get(s).copied().flatten())
+            .filter_map(|i| self.alphabet.chars().nth(i as usize))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn allotment() {
+        let homophonic = Homophonic::new(ENGLISH, "CIPHER", 100).unwrap();
+        let key = homophonic.key();
+        assert_eq!(key.len(), 100);
+        assert!(ENGLISH.chars().all(|c| key.contains(c)));
+        assert!(key.matches('E').count() > key.matches('T').count());
+        assert_eq!(key.matches('Z').count(), 1);
+        assert!(Homophonic::new(ENGLISH, "CIPHER", 20).is_err());
+    }
+
+    #[test]
+    fn homophonic() {
+        let plaintext = "ATTACKATDAWN";
+        let homophonic = Homophonic::new(ENGLISH, "CIPHER", 100).unwrap();
+        let ciphertxt = homophonic.encrypt(plaintext);
+        assert_eq!(ciphertxt, "061943320012536108682215");
+        assert!(homophonic.key().starts_with("CIPHERABDFGJKLMNOQSTUVWXYZ"));
+        assert_eq!(homophonic.decrypt(&ciphertxt), plaintext);
+
+        let restored = Homophonic::from_key(ENGLISH, &homophonic.key()).unwrap();
+        assert_eq!(restored.decrypt(&ciphertxt), plaintext);
+    }
+
+    #[test]
+    fn solve() {
+        let plaintext = filter(
+            &[
+                include_str!("kryptos/k2_plain.txt"),
+                include_str!("kryptos/k3_plain.txt"),
+            ]
+            .concat(),
+            ENGLISH,
+        );
+        let homophonic = Homophonic::new(ENGLISH, "CARTER", 40).unwrap();
+        let ciphertxt = homophonic.encrypt(&plaintext);
+        let solution = Homophonic::solve(ENGLISH, &ciphertxt, 4, 30000, Some(3));
+        let recovered = Homophonic::from_key(ENGLISH, &solution.key).unwrap();
+        let decrypted = recovered.decrypt(&ciphertxt);
+        let correct = decrypted
+            .chars()
+            .zip(plaintext.chars())
+            .filter(|(a, b)| a == b);
+        assert!(correct.count() as f64 > 0.8 * plaintext.len() as f64);
+
+        for ciphertxt in ["", "NO DIGITS HERE", "7"] {
+            let solution = Homophonic::solve(ENGLISH, ciphertxt, 1, 100, Some(3));
+            assert_eq!(solution.key, "");
+            assert_eq!(solution.score, f64::NEG_INFINITY);
+        }
+    }
+}
@@ -0,0 +1,327 @@
+use crate::common::{filter, keyed_alphabet, Cipher, ENGLISH, KRYPTOS};
+use crate::transpose::{key_order, Transpose};
+use crate::vigenere::Vigenere;
+use std::str::FromStr;
+
+pub const K1: &str = include_str!("kryptos/k1_cipher.txt");
+pub const K2: &str = include_str!("kryptos/k2_cipher.txt");
+pub const K3: &str = include_str!("kryptos/k3_cipher.txt");
+pub const K4: &str = include_str!("kryptos/k4_cipher.txt");
+pub const K1_PLAIN: &str = include_str!("kryptos/k1_plain.txt");
+pub const K2_PLAIN: &str = include_str!("kryptos/k2_plain.txt");
+pub const K3_PLAIN: &str = include_str!("kryptos/k3_plain.txt");
+
+// positions from 0 in the 97 letters of K4
+pub const CRIBS: [(usize, &str); 2] = [(21, "EASTNORTHEAST"), (63, "BERLINCLOCK")];
+
+pub fn letters(section: &str) -> String {
+    filter(section, ENGLISH)
+}
+
+pub fn matches_cribs(candidate: &str, cribs: &[(usize, &str)]) -> bool {
+    cribs
+        .iter()
+        .all(|&(at, crib)| candidate.get(at..at + crib.len()) == Some(crib))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Section {
+    K1,
+    K2,
+    K3,
+    All,
+}
+impl FromStr for Section {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "k1" => Ok(Section::K1),
+            "k2" => Ok(Section::K2),
+            "k3" => Ok(Section::K3),
+            "all" => Ok(Section::All),
+            _ => Err(format!("Unknown section: {}", s)),
+        }
+    }
+}
+impl Section {
+    pub fn solved(&self) -> Vec<Section> {
+        match self {
+            Section::All => vec![Section::K1, Section::K2, Section::K3],
+            section => vec![*section],
+        }
+    }
+
+    // K1 and K2 are Vigenère on the KRYPTOS alphabet, K3 a double rotation (8 then 24 rows)
+    pub fn decrypt(&self) -> String {
+        match self {
+            Section::K1 => Vigenere::new(KRYPTOS, "PALIMPSEST").unwrap().decrypt(K1),
+            Section::K2 => Vigenere::new(KRYPTOS, "ABSCISSA").unwrap().decrypt(K2),
+            Section::K3 => {
+                let halfway = Transpose::as_right(KRYPTOS, 8, false).unwrap().decrypt(K3);
+                Transpose::as_right(KRYPTOS, 24, false)
+                    .unwrap()
+                    .decrypt(&halfway)
+            }
+            Section::All => self
+                .solved()
+                .iter()
+                .map(|section| section.decrypt())
+                .collect(),
+        }
+    }
+
+    pub fn plaintext(&self) -> String {
+        match self {
+            Section::K1 => K1_PLAIN.to_string(),
+            Section::K2 => K2_PLAIN.to_string(),
+            Section::K3 => K3_PLAIN.to_string(),
+            Section::All => [K1_PLAIN, K2_PLAIN, K3_PLAIN].concat(),
+        }
+    }
+
+    // a difference in length counts from the end of the shorter text
+    pub fn check(&self) -> Vec<usize> {
+        let (found, expected) = (letters(&self.decrypt()), letters(&self.plaintext()));
+        let mut mismatches: Vec<usize> = found
+            .chars()
+            .zip(expected.chars())
+            .enumerate()
+            .filter(|(_, (a, b))| a != b)
+            .map(|(i, _)| i)
+            .collect();
+        mismatches.extend(found.len().min(expected.len())..found.len().max(expected.len()));
+        mismatches
+    }
+}
+
+// ciphertext letter k is letter positions[k] of the rows
+pub fn columnar(len: usize, order: &[usize]) -> Vec<usize> {
+    order
+        .iter()
+        .flat_map(|&col| (col..len).step_by(order.len()))
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Variant {
+    // c = p + k
+    Vigenere,
+    // c = k - p
+    Beaufort,
+    // c = p - k
+    VariantBeaufort,
+}
+impl Variant {
+    const ALL: [Variant; 3] = [
+        Variant::Vigenere,
+        Variant::Beaufort,
+        Variant::VariantBeaufort,
+    ];
+
+    fn key(&self, c: usize, p: usize) -> usize {
+        match self {
+            Variant::Vigenere => (c + 26 - p) % 26,
+            Variant::Beaufort => (c + p) % 26,
+            Variant::VariantBeaufort => (p + 26 - c) % 26,
+        }
+    }
+    fn plain(&self, c: usize, k: usize) -> usize {
+        match self {
+            Variant::Vigenere => (c + 26 - k) % 26,
+            Variant::Beaufort => (k + 26 - c) % 26,
+            Variant::VariantBeaufort => (c + k) % 26,
+        }
+    }
+}
+
+// `?` marks what the cribs leave open
+#[derive(Debug, Clone, PartialEq)]
+pub struct Candidate {
+    pub transposition: String,
+    pub alphabet: String,
+    pub variant: Variant,
+    pub period: usize,
+    pub key: String,
+    pub plaintext: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct Search {
+    // "" is the straight alphabet
+    pub alphabets: Vec<String>,
+    pub max_period: usize,
+    pub columns: Vec<String>,
+    pub max_width: usize,
+}
+impl Search {
+    pub fn new(alphabets: &str, max_period: usize, columns: &str, max_width: usize) -> Self {
+        let words = |s: &str| -> Vec<String> {
+            s.split(|c: char| c == ',' || c.is_whitespace())
+                .filter(|w| !w.is_empty())
+                .map(|w| w.to_uppercase())
+                .collect()
+        };
+        let mut alphabets = words(alphabets);
+        if alphabets.is_empty() {
+            alphabets.push(String::new());
+        }
+        let columns = words(columns).into_iter().filter(|w| w.len() > 1).collect();
+        Self {
+            alphabets,
+            max_period,
+            columns,
+            max_width,
+        }
+    }
+
+    fn transpositions(&self, len: usize) -> Vec<(String, Vec<usize>)> {
+        let mut transpositions = vec![(String::from("none"), (0..len).collect())];
+        for width in 2..=self.max_width.min(len) {
+            let order: Vec<usize> = (0..width).collect();
+            transpositions.push((format!("width {}", width), columnar(len, &order)));
+        }
+        for keyword in &self.columns {
+            let order = key_order(&filter(keyword, ENGLISH), ENGLISH);
+            transpositions.push((format!("columns {}", keyword), columnar(len, &order)));
+        }
+        transpositions
+    }
+
+    pub fn run(&self, ciphertxt: &str, cribs: &[(usize, &str)]) -> Vec<Candidate> {
+        let ciphertxt = letters(ciphertxt);
+        let len = ciphertxt.len();
+        let known: Vec<(usize, char)> = cribs
+            .iter()
+            .flat_map(|&(at, crib)| crib.chars().enumerate().map(move |(i, c)| (at + i, c)))
+            .filter(|&(at, _)| at < len)
+            .collect();
+
+        let mut candidates = Vec::new();
+        for (name, positions) in self.transpositions(len) {
+            for keyword in &self.alphabets {
+                let alphabet = keyed_alphabet(keyword, ENGLISH);
+                let index = |c: char| alphabet.find(c).unwrap();
+                let cipher: Vec<usize> = ciphertxt.chars().map(index).collect();
+                // where each plaintext letter lands in the ciphertext, and the position the key
+                // runs along when the substitution comes first or last
+                let mut placed = vec![0; len];
+                for (k, &p) in positions.iter().enumerate() {
+                    placed[p] = k;
+                }
+                let orders = match name.as_str() {
+                    "none" => vec![("", false)],
+                    _ => vec![(" then substitution", false), (" after substitution", true)],
+                };
+                for (order, substitute_first) in orders {
+                    let along = |p: usize| if substitute_first { p } else { placed[p] };
+                    for variant in Variant::ALL {
+                        for period in 1..=self.max_period {
+                            let mut key = vec![None; period];
+                            let consistent = known.iter().all(|&(p, c)| {
+                                let shift = variant.key(cipher[placed[p]], index(c));
+                                *key[along(p) % period].get_or_insert(shift) == shift
+                            });
+                            if !consistent {
+                                continue;
+                            }
+                            let letter = |i: usize| alphabet.chars().nth(i).unwrap();
+                            let plaintext: String = (0..len)
+                                .map(|p| match key[along(p) % period] {
+                                    Some(k) => letter(variant.plain(cipher[placed[p]], k)),
+                                    None => '?',
+                                })
+                                .collect();
+                            candidates.push(Candidate {
+                                transposition: format!("{}{}", name, order),
+                                alphabet: alphabet.clone(),
+                                variant,
+                                period,
+                                key: key.iter().map(|k| k.map_or('?', letter)).collect(),
+                                plaintext,
+                            });
+                            // a longer period only loosens the fit, so report the shortest
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+        candidates
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sections() {
+        let k4 = letters(K4);
+        assert_eq!(k4.len(), 97);
+        assert_eq!(&k4[21..25], "FLRV");
+        assert_eq!(&k4[63..69], "NYPVTT");
+        assert_eq!(letters(K1).len(), letters(K1_PLAIN).len());
+        assert_eq!(letters(K3).len(), letters(K3_PLAIN).len());
+        assert!(matches_cribs(&"A".repeat(21), &[(0, "AAA")]));
+        assert!(!matches_cribs(&"A".repeat(21), &CRIBS));
+    }
+
+    #[test]
+    fn solved() {
+        for section in Section::All.solved() {
+            assert_eq!(section.check(), Vec::<usize>::new());
+        }
+        assert!(Section::K1.decrypt().starts_with("BETWEENSUBTLESHADING"));
+        assert!(letters(&Section::K3.decrypt()).ends_with("CANYOUSEEANYTHINGQ"));
+        assert_eq!("ALL".parse::<Section>(), Ok(Section::All));
+        assert!("k4".parse::<Section>().is_err());
+    }
+
+    #[test]
+    fn k2_key() {
+        // the opening of K2 is enough to recover ABSCISSA on the KRYPTOS alphabet
+        let search = Search::new("KRYPTOS", 10, "", 0);
+        let candidates = search.run(K2, &[(0, "ITWASTOTALLYINVISIBLEHOWS")]);
+        let found = candidates
+            .iter()
+            .find(|c| c.variant == Variant::Vigenere)
+            .unwrap();
+        assert_eq!(found.alphabet, KRYPTOS);
+        assert_eq!((found.period, found.key.as_str()), (8, "ABSCISSA"));
+        assert!(found.plaintext.starts_with("ITWASTOTALLYINVISIBLE"));
+    }
+
+    #[test]
+    fn transposed() {
+        // a K4-sized message enciphered on the KRYPTOS alphabet, then written under a key
+        let plaintext = format!(
+            "{}{}{}{}{}",
+            "SLOWLYTHEREMAINSOFTHE",
+            CRIBS[0].1,
+            "WEREREMOVEDFROMTHECHAMBERANDA",
+            CRIBS[1].1,
+            "SHOWEDTHETIMEANDPLACEXX"
+        );
+        let substituted = Vigenere::new(KRYPTOS, "SHADOW")
+            .unwrap()
+            .encrypt(&plaintext);
+        let order = key_order("PALIMPSEST", ENGLISH);
+        let ciphertxt: String = columnar(plaintext.len(), &order)
+            .into_iter()
+            .map(|p| substituted.chars().nth(p).unwrap())
+            .collect();
+
+        let search = Search::new("KRYPTOS", 8, "PALIMPSEST ABSCISSA", 4);
+        let candidates = search.run(&ciphertxt, &CRIBS);
+        assert!(candidates
+            .iter()
+            .all(|c| matches_cribs(&c.plaintext, &CRIBS)));
+        let found = candidates
+            .iter()
+            .find(|c| c.transposition == "columns PALIMPSEST after substitution")
+            .unwrap();
+        assert_eq!((found.period, found.key.as_str()), (6, "SHADOW"));
+        assert_eq!(found.plaintext, plaintext);
+        assert!(Search::new("KRYPTOS", 8, "", 0).run(K4, &CRIBS).is_empty());
+    }
+}
@@ -1,6 +1,33 @@
 pub mod common;
 
 pub mod affine;
+pub mod alberti;
 pub mod autokey;
+pub mod baconian;
+pub mod baudot;
+pub mod bombe;
+pub mod chaocipher;
+pub mod cylinder;
+pub mod digraph;
+pub mod enigma;
+pub mod feistel;
+pub mod grandpre;
+pub mod gromark;
+pub mod homophonic;
+pub mod kryptos;
+pub mod lorenz;
+pub mod m209;
+pub mod morse;
+pub mod nicodemus;
+pub mod polybius;
+pub mod ragbaby;
+pub mod rotor;
+pub mod solitaire;
+pub mod stats;
+pub mod substitution;
+pub mod syllabary;
+pub mod tapcode;
 pub mod transpose;
+pub mod typex;
+pub mod vic;
 pub mod vigenere;
@@ -0,0 +1,362 @@
+use crate::baudot::{decode, encode, from_tape, to_tape};
+use crate::common::Cipher;
+use crate::stats::CORPUS;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+pub const CHI: [usize; 5] = [41, 31, 29, 26, 23];
+pub const PSI: [usize; 5] = [43, 47, 51, 53, 59];
+pub const MU: [usize; 2] = [61, 37];
+pub const NAMES: [&str; 12] = [
+    "chi1", "chi2", "chi3", "chi4", "chi5", "psi1", "psi2", "psi3", "psi4", "psi5", "mu61", "mu37",
+];
+
+// impulses count from 0
+fn impulse(code: u8, k: usize) -> bool {
+    code >> (4 - k) & 1 == 1
+}
+fn parse_pattern(pattern: &str, len: usize) -> Result<Vec<bool>, String> {
+    let pins: Vec<bool> = pattern
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| match c {
+            'x' | 'X' | '1' => Ok(true),
+            '.' | '0' => Ok(false),
+            _ => Err(format!("Invalid pin: {}", c)),
+        })
+        .collect::<Result<Vec<bool>, String>>()?;
+    match pins.len() == len {
+        true => Ok(pins),
+        false => Err(format!("Wheel of length {} has {} pins!", len, pins.len())),
+    }
+}
+
+// bias of each impulse's delta (diagonal) and of each pair's summed deltas
+fn delta_bias(codes: &[u8]) -> [[f64; 5]; 5] {
+    let n = codes.len().saturating_sub(1).max(1) as f64;
+    let mut bias = [[0.0; 5]; 5];
+    for (a, row) in bias.iter_mut().enumerate() {
+        for (b, value) in row.iter_mut().enumerate() {
+            let dots = codes.windows(2).filter(|w| {
+                let delta = |k| impulse(w[0], k) ^ impulse(w[1], k);
+                match a == b {
+                    true => !delta(a),
+                    false => delta(a) == delta(b),
+                }
+            });
+            *value = dots.count() as f64 / n - 0.5;
+        }
+    }
+    bias
+}
+
+#[derive(Debug, Clone)]
+pub struct Lorenz {
+    wheels: Vec<Vec<bool>>,
+    start: Vec<usize>,
+}
+impl Lorenz {
+    // `x` is a raised cam, `.` a lowered one
+    pub fn new(patterns: &[&str]) -> Result<Self, String> {
+        let lengths = [&CHI[..], &PSI[..], &MU[..]].concat();
+        if patterns.len() != lengths.len() {
+            return Err(format!(
+                "Patterns must be given for all {} wheels!",
+                lengths.len()
+            ));
+        }
+        let wheels = patterns
+            .iter()
+            .zip(lengths.iter())
+            .map(|(pattern, &len)| parse_pattern(pattern, len))
+            .collect::<Result<Vec<Vec<bool>>, String>>()?;
+        Ok(Self {
+            wheels,
+            start: vec![0; lengths.len()],
+        })
+    }
+    pub fn random(seed: Option<u64>) -> Self {
+        let mut rng = match seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        let lengths = [&CHI[..], &PSI[..], &MU[..]].concat();
+        Self {
+            wheels: lengths
+                .iter()
+                .map(|&len| (0..len).map(|_| rng.gen_bool(0.5)).collect())
+                .collect(),
+            start: vec![0; lengths.len()],
+        }
+    }
+    pub fn from_key_list(key_list: &str) -> Result<Self, String> {
+        let mut patterns = vec![String::new(); NAMES.len()];
+        let mut start = None;
+        for line in key_list.lines() {
+            let line = line.split('#').next().unwrap().trim();
+            let Some((name, value)) = line.split_once(':') else {
+                continue;
+            };
+            match NAMES.iter().position(|&n| n == name.trim().to_lowercase()) {
+                Some(i) => patterns[i] = value.trim().to_string(),
+                None if name.trim() == "start" => start = Some(value.trim().to_string()),
+                None => return Err(format!("Unknown key list entry: {}", name)),
+            }
+        }
+        let patterns: Vec<&str> = patterns.iter().map(|p| p.as_str()).collect();
+        let mut lorenz = Self::new(&patterns)?;
+        if let Some(start) = start {
+            let start = start
+                .split_whitespace()
+                .map(|s| s.parse().map_err(|_| format!("Invalid position: {}", s)))
+                .collect::<Result<Vec<usize>, String>>()?;
+            lorenz.set_start(&start)?;
+        }
+        Ok(lorenz)
+    }
+    pub fn key_list(&self) -> String {
+        let mut lines: Vec<String> = NAMES
+            .iter()
+            .zip(self.wheels.iter())
+            .map(|(name, wheel)| {
+                let pattern: String = wheel
+                    .iter()
+                    .map(|&p| match p {
+                        true => 'x',
+                        false => '.',
+                    })
+                    .collect();
+                format!("{}: {}", name, pattern)
+            })
+            .collect();
+        let start: Vec<String> = self.start().iter().map(|s| s.to_string()).collect();
+        lines.push(format!("start: {}", start.join(" ")));
+        lines.join("\n")
+    }
+
+    pub fn start(&self) -> Vec<usize> {
+        self.start.iter().map(|s| s + 1).collect()
+    }
+    pub fn set_start(&mut self, start: &[usize]) -> Result<(), String> {
+        if start.len() != self.wheels.len() {
+            return Err(format!(
+                "Start must give all {} positions!",
+                self.wheels.len()
+            ));
+        }
+        if start
+            .iter()
+            .zip(self.wheels.iter())
+            .any(|(&s, wheel)| s == 0 || s > wheel.len())
+        {
+            return Err(String::from("Start position is not on the wheel!"));
+        }
+        self.start = start.iter().map(|s| s - 1).collect();
+        Ok(())
+    }
+
+    fn chi(&self, k: usize, position: usize) -> bool {
+        self.wheels[k][position % CHI[k]]
+    }
+    // mu61 steps every letter, mu37 when mu61 shows a cross, the psis when mu37 does
+    fn key(&self, len: usize) -> Vec<u8> {
+        let mut pos = self.start.clone();
+        let mut key = Vec::with_capacity(len);
+        for _ in 0..len {
+            let code = (0..5).fold(0, |code, k| {
+                let bit = self.wheels[k][pos[k]] ^ self.wheels[5 + k][pos[5 + k]];
+                code << 1 | bit as u8
+            });
+            key.push(code);
+
+            let (basic, total) = (self.wheels[10][pos[10]], self.wheels[11][pos[11]]);
+            let mut steps = vec![true; 5];
+            steps.extend([total; 5]);
+            steps.extend([true, basic]);
+            for (w, step) in steps.into_iter().enumerate() {
+                if step {
+                    pos[w] = (pos[w] + 1) % self.wheels[w].len();
+                }
+            }
+        }
+        key
+    }
+    fn add(&self, codes: Vec<u8>) -> Vec<u8> {
+        let key = self.key(codes.len());
+        codes.into_iter().zip(key).map(|(c, k)| c ^ k).collect()
+    }
+    pub fn dechi(&self, ciphertxt: &str) -> String {
+        let codes: Vec<u8> = from_tape(ciphertxt)
+            .into_iter()
+            .enumerate()
+            .map(|(i, code)| {
+                (0..5).fold(code, |code, k| {
+                    code ^ (self.chi(k, self.start[k] + i) as u8) << (4 - k)
+                })
+            })
+            .collect();
+        to_tape(&codes)
+    }
+
+    // sets the most biased pair of wheels together, then each of the others
+    pub fn find_chi(&self, ciphertxt: &str) -> Vec<usize> {
+        let bias = delta_bias(&encode(CORPUS));
+        let z = from_tape(ciphertxt);
+        let half = z.len().saturating_sub(1) as f64 / 2.0;
+        // the delta of de-chied impulse k at each step, for a chi start position
+        let delta_d = |k: usize, start: usize| -> Vec<bool> {
+            z.windows(2)
+                .enumerate()
+                .map(|(i, w)| {
+                    let dchi = self.chi(k, start + i) ^ self.chi(k, start + i + 1);
+                    impulse(w[0], k) ^ impulse(w[1], k) ^ dchi
+                })
+                .collect()
+        };
+        let excess = |dots: usize| dots as f64 - half;
+        let single = |a: &[bool]| excess(a.iter().filter(|&&x| !x).count());
+        let pair = |a: &[bool], b: &[bool]| excess(a.iter().zip(b).filter(|(x, y)| x == y).count());
+
+        let (a, b) = (0..5)
+            .flat_map(|a| (a + 1..5).map(move |b| (a, b)))
+            .max_by(|&(a, b), &(c, d)| bias[a][b].abs().total_cmp(&bias[c][d].abs()))
+            .unwrap();
+        let mut set: Vec<Option<(usize, Vec<bool>)>> = vec![None; 5];
+        let mut best = f64::NEG_INFINITY;
+        let firsts: Vec<Vec<bool>> = (0..CHI[a]).map(|s| delta_d(a, s)).collect();
+        for s2 in 0..CHI[b] {
+            let second = delta_d(b, s2);
+            for (s1, first) in firsts.iter().enumerate() {
+                let score = bias[a][b] * pair(first, &second)
+                    + bias[a][a] * single(first)
+                    + bias[b][b] * single(&second);
+                if score > best {
+                    best = score;
+                    set[a] = Some((s1, first.clone()));
+                    set[b] = Some((s2, second.clone()));
+                }
+            }
+        }
+
+        while let Some(k) = (0..5).find(|&k| set[k].is_none()) {
+            set[k] = (0..CHI[k])
+                .map(|s| (s, delta_d(k, s)))
+                .max_by(|(_, x), (_, y)| {
+                    let score = |other: &[bool]| {
+                        let pairs = set.iter().enumerate().filter_map(|(j, d)| {
+                            d.as_ref().map(|(_, d)| bias[j][k] * pair(d, other))
+                        });
+                        bias[k][k] * single(other) + pairs.sum::<f64>()
+                    };
+                    score(x).total_cmp(&score(y))
+                });
+        }
+        let mut starts: Vec<usize> = set.into_iter().map(|d| d.unwrap().0).collect();
+
+        // re-set each wheel against the others on the whole de-chied delta, half of which
+        // is expected to follow English and half to be blurred by moving psi wheels
+        let mut counts = [1.0; 32];
+        let plain = encode(CORPUS);
+        plain
+            .windows(2)
+            .for_each(|w| counts[(w[0] ^ w[1]) as usize] += 1.0);
+        let total: f64 = counts.iter().sum();
+        let logq: Vec<f64> = counts
+            .iter()
+            .map(|c| (0.5 * c / total + 0.5 / 32.0).ln())
+            .collect();
+        let delta_z: Vec<u8> = z.windows(2).map(|w| w[0] ^ w[1]).collect();
+        for _ in 0..2 {
+            for k in 0..5 {
+                let fixed: Vec<u8> = (0..delta_z.len())
+                    .map(|i| {
+                        (0..5).filter(|&j| j != k).fold(delta_z[i], |code, j| {
+                            let dchi = self.chi(j, starts[j] + i) ^ self.chi(j, starts[j] + i + 1);
+                            code ^ (dchi as u8) << (4 - j)
+                        })
+                    })
+                    .collect();
+                let score = |s: usize| -> f64 {
+                    fixed
+                        .iter()
+                        .enumerate()
+                        .map(|(i, &code)| {
+                            let dchi = self.chi(k, s + i) ^ self.chi(k, s + i + 1);
+                            logq[(code ^ (dchi as u8) << (4 - k)) as usize]
+                        })
+                        .sum()
+                };
+                starts[k] = (0..CHI[k])
+                    .max_by(|&x, &y| score(x).total_cmp(&score(y)))
+                    .unwrap();
+            }
+        }
+        starts.into_iter().map(|s| s + 1).collect()
+    }
+}
+
+impl Cipher for Lorenz {
+    fn encrypt(&self, plaintext: &str) -> String {
+        to_tape(&self.add(encode(plaintext)))
+    }
+    fn decrypt(&self, ciphertxt: &str) -> String {
+        decode(&self.add(from_tape(ciphertxt)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lorenz() {
+        let lorenz = Lorenz::random(Some(1942));
+        let plaintext = "SEND 25 TANKS TO POSITION 4.";
+        let ciphertxt = lorenz.encrypt(plaintext);
+        assert_eq!(ciphertxt.len(), 31);
+        assert_eq!(lorenz.decrypt(&ciphertxt), plaintext);
+    }
+
+    #[test]
+    fn dechi() {
+        let lengths = [&CHI[..], &PSI[..], &MU[..]].concat();
+        let patterns: Vec<String> = lengths
+            .iter()
+            .enumerate()
+            .map(|(w, &len)| match w {
+                5..=9 => ".".repeat(len),
+                _ => "x.".repeat(len).chars().take(len).collect(),
+            })
+            .collect();
+        let patterns: Vec<&str> = patterns.iter().map(|p| p.as_str()).collect();
+        let lorenz = Lorenz::new(&patterns).unwrap();
+        let ciphertxt = lorenz.encrypt("ATTACK AT DAWN");
+        assert_eq!(lorenz.dechi(&ciphertxt), to_tape(&encode("ATTACK AT DAWN")));
+    }
+
+    #[test]
+    fn key_list() {
+        let mut lorenz = Lorenz::random(Some(1942));
+        lorenz
+            .set_start(&[3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5, 8])
+            .unwrap();
+        let restored = Lorenz::from_key_list(&lorenz.key_list()).unwrap();
+        assert_eq!(restored.start(), lorenz.start());
+        assert_eq!(restored.encrypt("ATTACK"), lorenz.encrypt("ATTACK"));
+        assert!(lorenz.set_start(&[42; 12]).is_err());
+        assert!(Lorenz::new(&["x.x"; 12]).is_err());
+    }
+
+    #[test]
+    fn find_chi() {
+        let mut lorenz = Lorenz::random(Some(1944));
+        let start = [7, 19, 2, 25, 11, 1, 1, 1, 1, 1, 1, 1];
+        lorenz.set_start(&start).unwrap();
+        let ciphertxt = lorenz.encrypt(&CORPUS[..6000]);
+        let mut receiver = Lorenz::random(Some(1944));
+        let found = receiver.find_chi(&ciphertxt);
+        assert_eq!(found, start[..5]);
+        receiver.set_start(&start).unwrap();
+        assert_ne!(receiver.dechi(&ciphertxt), ciphertxt);
+    }
+}
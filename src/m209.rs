@@ -0,0 +1,349 @@
+use crate::common::{char_index, filter, refill, Cipher, ENGLISH};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+pub const WHEELS: [&str; 6] = [
+    "ABCDEFGHIJKLMNOPQRSTUVWXYZ",
+    "ABCDEFGHIJKLMNOPQRSTUVXYZ",
+    "ABCDEFGHIJKLMNOPQRSTUVX",
+    "ABCDEFGHIJKLMNOPQRSTU",
+    "ABCDEFGHIJKLMNOPQRS",
+    "ABCDEFGHIJKLMNOPQ",
+];
+pub const N_BARS: usize = 27;
+
+// the guide arms sense the pin this far past the one in the window
+const OFFSETS: [usize; 6] = [15, 14, 13, 12, 11, 10];
+
+// 0 is an empty lug position
+fn lug_types() -> Vec<(usize, usize)> {
+    let mut types = vec![(0, 0)];
+    types.extend((1..=6).map(|w| (0, w)));
+    for a in 1..=6 {
+        types.extend((a + 1..=6).map(|b| (a, b)));
+    }
+    types
+}
+fn engages(lug: (usize, usize), mask: usize) -> bool {
+    [lug.0, lug.1]
+        .iter()
+        .any(|&w| w > 0 && mask >> (w - 1) & 1 == 1)
+}
+
+fn parse_position(position: &str) -> Result<Vec<usize>, String> {
+    let position = filter(position, ENGLISH);
+    if position.len() != 6 {
+        return Err(String::from("Position must have 6 letters!"));
+    }
+    position
+        .chars()
+        .zip(WHEELS.iter())
+        .map(|(c, wheel)| wheel.chars().position(|x| x == c))
+        .collect::<Option<Vec<usize>>>()
+        .ok_or(String::from("Position is not on the wheels!"))
+}
+
+#[derive(Debug, Clone)]
+pub struct M209 {
+    pins: Vec<Vec<bool>>,
+    lugs: Vec<(usize, usize)>,
+    position: Vec<usize>,
+}
+impl M209 {
+    pub fn new(pins: &[&str], lugs: &str, position: &str) -> Result<Self, String> {
+        if pins.len() != 6 {
+            return Err(String::from("Pins must be given for all 6 wheels!"));
+        }
+        let pins = pins
+            .iter()
+            .zip(WHEELS.iter())
+            .map(
+                |(active, wheel)| match active.chars().all(|c| wheel.contains(c)) {
+                    true => Ok(wheel.chars().map(|c| active.contains(c)).collect()),
+                    false => Err(String::from("Pins contain invalid characters!")),
+                },
+            )
+            .collect::<Result<Vec<Vec<bool>>, String>>()?;
+
+        let lugs = lugs
+            .split_whitespace()
+            .map(|bar| {
+                let wheels: Vec<usize> = bar.split('-').filter_map(|w| w.parse().ok()).collect();
+                match wheels[..] {
+                    [a, b] if a <= 6 && b <= 6 => Ok((a.min(b), a.max(b))),
+                    _ => Err(format!("Invalid lug setting: {}", bar)),
+                }
+            })
+            .collect::<Result<Vec<(usize, usize)>, String>>()?;
+        if lugs.len() != N_BARS {
+            return Err(format!("Lugs must be given for all {} bars!", N_BARS));
+        }
+
+        Ok(Self {
+            pins,
+            lugs,
+            position: parse_position(position)?,
+        })
+    }
+    pub fn set_position(&mut self, position: &str) -> Result<(), String> {
+        self.position = parse_position(position)?;
+        Ok(())
+    }
+
+    pub fn from_key_list(key_list: &str) -> Result<Self, String> {
+        let mut pins = vec![String::new(); 6];
+        let (mut lugs, mut position) = (String::new(), String::from("AAAAAA"));
+        for line in key_list.lines() {
+            let line = line.split('#').next().unwrap().trim();
+            let Some((name, value)) = line.split_once(':') else {
+                continue;
+            };
+            let value = value.trim().to_string();
+            match name.trim().to_lowercase().as_str() {
+                "lugs" => lugs = value,
+                "position" => position = value,
+                name => match name.strip_prefix("wheel").map(|n| n.trim().parse()) {
+                    Some(Ok(n @ 1..=6)) => pins[n - 1] = value.replace(' ', ""),
+                    _ => return Err(format!("Unknown key list entry: {}", name)),
+                },
+            }
+        }
+        let pins: Vec<&str> = pins.iter().map(|p| p.as_str()).collect();
+        Self::new(&pins, &lugs, &position)
+    }
+    pub fn key_list(&self) -> String {
+        let mut lines: Vec<String> = (0..6)
+            .map(|w| {
+                let active: String = WHEELS[w]
+                    .chars()
+                    .zip(self.pins[w].iter())
+                    .filter_map(|(c, &p)| p.then_some(c))
+                    .collect();
+                format!("wheel {}: {}", w + 1, active)
+            })
+            .collect();
+        let lugs: Vec<String> = self
+            .lugs
+            .iter()
+            .map(|(a, b)| format!("{}-{}", a, b))
+            .collect();
+        lines.push(format!("lugs: {}", lugs.join(" ")));
+        let position: String = (0..6)
+            .map(|w| WHEELS[w].chars().nth(self.position[w]).unwrap())
+            .collect();
+        lines.push(format!("position: {}", position));
+        lines.join("\n")
+    }
+
+    // bitmask over the wheels
+    fn mask(pins: &[Vec<bool>], position: &[usize], step: usize) -> usize {
+        (0..6)
+            .filter(|&w| {
+                let len = pins[w].len();
+                pins[w][(position[w] + step + OFFSETS[w]) % len]
+            })
+            .fold(0, |mask, w| mask | 1 << w)
+    }
+    fn displacement(&self, step: usize) -> u32 {
+        let mask = Self::mask(&self.pins, &self.position, step);
+        self.lugs.iter().filter(|&&lug| engages(lug, mask)).count() as u32
+    }
+
+    pub fn recover(
+        plaintext: &str,
+        ciphertxt: &str,
+        position: &str,
+        iterations: usize,
+        seed: Option<u64>,
+    ) -> Result<Self, String> {
+        let mut rng = match seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        let lugs = vec!["0-0"; N_BARS].join(" ");
+        let mut machine = Self::new(&[""; 6], &lugs, position)?;
+        let plain = char_index(&filter(plaintext, ENGLISH), ENGLISH);
+        let cipher = char_index(&filter(ciphertxt, ENGLISH), ENGLISH);
+        let observed: Vec<u32> = plain
+            .iter()
+            .zip(cipher.iter())
+            .map(|(p, c)| (p + c + 1) % 26)
+            .collect();
+        if observed.is_empty() {
+            return Err(String::from("Crib must not be empty!"));
+        }
+
+        let types = lug_types();
+        // bars engaged for every combination of active pins
+        let tabulate = |counts: &[usize]| -> Vec<u32> {
+            (0..64)
+                .map(|mask| {
+                    let engaged = types.iter().zip(counts.iter());
+                    engaged
+                        .filter(|(&lug, _)| engages(lug, mask))
+                        .map(|(_, &n)| n as u32)
+                        .sum()
+                })
+                .collect()
+        };
+        let score = |masks: &[usize], table: &[u32]| -> f64 {
+            let errors: u32 = masks
+                .iter()
+                .zip(observed.iter())
+                .map(|(&mask, &k)| {
+                    let d = (table[mask] + 26 - k) % 26;
+                    d.min(26 - d).pow(2)
+                })
+                .sum();
+            -(errors as f64)
+        };
+        // flipping a pin toggles its wheel's bit at every step that senses it
+        let flip = |pins: &mut [Vec<bool>], masks: &mut [usize], w: usize, i: usize| {
+            pins[w][i] = !pins[w][i];
+            let len = pins[w].len();
+            let first = (i + 2 * len - machine.position[w] % len - OFFSETS[w] % len) % len;
+            for step in (first..masks.len()).step_by(len) {
+                masks[step] ^= 1 << w;
+            }
+        };
+
+        let mut counts = vec![0; types.len()];
+        let (mut best_pins, mut best_counts, mut best_score) =
+            (machine.pins.clone(), counts.clone(), f64::NEG_INFINITY);
+        let mut pins = machine.pins.clone();
+        'restarts: for _ in 0..16 {
+            for wheel in pins.iter_mut() {
+                wheel.iter_mut().for_each(|p| *p = rng.gen_bool(0.5));
+            }
+            let mut masks: Vec<usize> = (0..observed.len())
+                .map(|step| Self::mask(&pins, &machine.position, step))
+                .collect();
+            counts.iter_mut().for_each(|n| *n = 0);
+            for _ in 0..N_BARS {
+                counts[rng.gen_range(1..7)] += 1;
+            }
+            let mut table = tabulate(&counts);
+            let mut current = score(&masks, &table);
+            for step in 0..iterations {
+                let temperature = 4.0 * (1.0 - step as f64 / iterations as f64);
+                let mut candidate_table = None;
+                let pin = match rng.gen_bool(0.5) {
+                    false => {
+                        let w = rng.gen_range(0..6);
+                        let i = rng.gen_range(0..pins[w].len());
+                        flip(&mut pins, &mut masks, w, i);
+                        Ok((w, i))
+                    }
+                    true => {
+                        let from = rng.gen_range(0..types.len());
+                        if counts[from] == 0 {
+                            continue;
+                        }
+                        let to = rng.gen_range(0..types.len());
+                        counts[from] -= 1;
+                        counts[to] += 1;
+                        candidate_table = Some(tabulate(&counts));
+                        Err((from, to))
+                    }
+                };
+                let candidate = score(&masks, candidate_table.as_ref().unwrap_or(&table));
+                let delta = candidate - current;
+                if delta >= 0.0 || rng.gen::<f64>() < (delta / temperature.max(1e-9)).exp() {
+                    current = candidate;
+                    if let Some(candidate_table) = candidate_table {
+                        table = candidate_table;
+                    }
+                } else {
+                    match pin {
+                        Ok((w, i)) => flip(&mut pins, &mut masks, w, i),
+                        Err((from, to)) => {
+                            counts[to] -= 1;
+                            counts[from] += 1;
+                        }
+                    }
+                }
+                if current > best_score {
+                    (best_pins, best_counts, best_score) = (pins.clone(), counts.clone(), current);
+                    if best_score == 0.0 {
+                        break 'restarts;
+                    }
+                }
+            }
+        }
+
+        machine.pins = best_pins;
+        machine.lugs = types
+            .iter()
+            .zip(best_counts.iter())
+            .flat_map(|(&lug, &n)| std::iter::repeat_n(lug, n))
+            .collect();
+        Ok(machine)
+    }
+}
+impl Cipher for M209 {
+    fn encrypt(&self, plaintext: &str) -> String {
+        let output: String = char_index(&filter(plaintext, ENGLISH), ENGLISH)
+            .into_iter()
+            .enumerate()
+            .map(|(step, p)| {
+                let c = (25 + self.displacement(step) + 26 - p) % 26;
+                ENGLISH.chars().nth(c as usize).unwrap()
+            })
+            .collect();
+        refill(&output, plaintext, ENGLISH)
+    }
+    fn decrypt(&self, ciphertxt: &str) -> String {
+        self.encrypt(ciphertxt)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY_LIST: &str = include_str!("m209/key_list.txt");
+
+    #[test]
+    fn reciprocal() {
+        let lugs = vec!["0-0"; N_BARS].join(" ");
+        let m209 = M209::new(&[""; 6], &lugs, "AAAAAA").unwrap();
+        assert_eq!(m209.encrypt("ABC XYZ"), "ZYX CBA");
+
+        let lugs = vec!["1-2"; N_BARS].join(" ");
+        let m209 = M209::new(&WHEELS, &lugs, "AAAAAA").unwrap();
+        assert_eq!(m209.encrypt("ABC XYZ"), "AZY DCB");
+        assert!(M209::new(&WHEELS, "1-2", "AAAAAA").is_err());
+        assert!(M209::new(&WHEELS, &lugs, "ZZZZZZ").is_err());
+    }
+
+    #[test]
+    fn key_list() {
+        let m209 = M209::from_key_list(KEY_LIST).unwrap();
+        let plaintext = "ATTACK AT DAWN WITH ALL AVAILABLE UNITS";
+        let ciphertxt = m209.encrypt(plaintext);
+        assert_ne!(ciphertxt, plaintext);
+        assert_eq!(m209.decrypt(&ciphertxt), plaintext);
+
+        let mut restored = M209::from_key_list(&m209.key_list()).unwrap();
+        assert_eq!(restored.encrypt(plaintext), ciphertxt);
+        restored.set_position("BBBBBB").unwrap();
+        assert_ne!(restored.encrypt(plaintext), ciphertxt);
+        assert!(M209::from_key_list("wheel 7: ABC").is_err());
+    }
+
+    #[test]
+    fn recover() {
+        let m209 = M209::from_key_list(KEY_LIST).unwrap();
+        let plaintext = filter(include_str!("kryptos/k3_plain.txt"), ENGLISH);
+        let ciphertxt = m209.encrypt(&plaintext);
+        let (crib, rest) = plaintext.split_at(150);
+        let recovered =
+            M209::recover(crib, &ciphertxt[..150], "AAAAAA", 20000, Some(1943)).unwrap();
+        let decrypted = recovered.decrypt(&ciphertxt);
+        let correct = decrypted[150..]
+            .chars()
+            .zip(rest.chars())
+            .filter(|(a, b)| a == b);
+        assert!(correct.count() as f64 > 0.9 * rest.len() as f64);
+    }
+}
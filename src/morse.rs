@@ -0,0 +1,265 @@
+use crate::common::{char_index, check_unique, keyed_alphabet, Cipher};
+use crate::transpose::key_digits;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+pub const SYMBOLS: &str = ".-x";
+pub const MORSE: [(char, &str); 36] = [
+    ('A', ".-"),
+    ('B', "-..."),
+    ('C', "-.-."),
+    ('D', "-.."),
+    ('E', "."),
+    ('F', "..-."),
+    ('G', "--."),
+    ('H', "...."),
+    ('I', ".."),
+    ('J', ".---"),
+    ('K', "-.-"),
+    ('L', ".-.."),
+    ('M', "--"),
+    ('N', "-."),
+    ('O', "---"),
+    ('P', ".--."),
+    ('Q', "--.-"),
+    ('R', ".-."),
+    ('S', "..."),
+    ('T', "-"),
+    ('U', "..-"),
+    ('V', "...-"),
+    ('W', ".--"),
+    ('X', "-..-"),
+    ('Y', "-.--"),
+    ('Z', "--.."),
+    ('0', "-----"),
+    ('1', ".----"),
+    ('2', "..---"),
+    ('3', "...--"),
+    ('4', "....-"),
+    ('5', "....."),
+    ('6', "-...."),
+    ('7', "--..."),
+    ('8', "---.."),
+    ('9', "----."),
+];
+
+pub fn encode(input: &str) -> String {
+    input
+        .split_whitespace()
+        .map(|word| {
+            word.chars()
+                .filter_map(|c| MORSE.iter().find(|(x, _)| *x == c).map(|(_, code)| *code))
+                .collect::<Vec<&str>>()
+                .join("x")
+        })
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<String>>()
+        .join("xx")
+}
+
+pub fn decode(input: &str) -> String {
+    input
+        .trim_matches('x')
+        .split("xx")
+        .map(|word| {
+            word.split('x')
+                .filter_map(|code| MORSE.iter().find(|(_, x)| *x == code).map(|(c, _)| *c))
+                .collect::<String>()
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+fn symbol_groups(size: u32) -> Vec<String> {
+    let mut groups = vec![String::new()];
+    for _ in 0..size {
+        groups = groups
+            .iter()
+            .flat_map(|g| SYMBOLS.chars().map(move |s| format!("{}{}", g, s)))
+            .collect();
+    }
+    groups
+}
+
+#[derive(Debug)]
+pub struct FractionatedMorse {
+    alphabet: String,
+    triples: Vec<String>,
+}
+impl FractionatedMorse {
+    pub fn new(alphabet: &str, keyword: &str) -> Result<Self, String> {
+        check_unique(alphabet).unwrap();
+        let alphabet = keyed_alphabet(keyword, alphabet);
+        let triples: Vec<String> = symbol_groups(3)
+            .into_iter()
+            .filter(|t| t != "xxx")
+            .collect();
+        if alphabet.chars().count() != triples.len() {
+            return Err(format!("Alphabet must have {} characters!", triples.len()));
+        }
+        Ok(Self { alphabet, triples })
+    }
+}
+impl Cipher for FractionatedMorse {
+    fn encrypt(&self, plaintext: &str) -> String {
+        let mut morse = encode(plaintext);
+        while !morse.len().is_multiple_of(3) {
+            morse.push('x');
+        }
+        (0..morse.len())
+            .step_by(3)
+            .filter_map(|i| self.triples.iter().position(|t| *t == morse[i..i + 3]))
+            .filter_map(|i| self.alphabet.chars().nth(i))
+            .collect()
+    }
+    fn decrypt(&self, ciphertxt: &str) -> String {
+        let morse: String = char_index(ciphertxt, &self.alphabet)
+            .into_iter()
+            .map(|i| self.triples[i as usize].as_str())
+            .collect();
+        decode(&morse)
+    }
+}
+
+#[derive(Debug)]
+pub struct Morbit {
+    digits: Vec<u32>,
+}
+impl Morbit {
+    pub fn new(alphabet: &str, keyword: &str) -> Result<Self, String> {
+        check_unique(alphabet).unwrap();
+        if keyword.chars().count() != 9 || !keyword.chars().all(|c| alphabet.contains(c)) {
+            return Err(String::from("Keyword must have 9 valid characters!"));
+        }
+        Ok(Self {
+            digits: key_digits(keyword, alphabet),
+        })
+    }
+}
+impl Cipher for Morbit {
+    fn encrypt(&self, plaintext: &str) -> String {
+        let mut morse = encode(plaintext);
+        if !morse.len().is_multiple_of(2) {
+            morse.push('x');
+        }
+        let pairs = symbol_groups(2);
+        (0..morse.len())
+            .step_by(2)
+            .filter_map(|i| pairs.iter().position(|p| *p == morse[i..i + 2]))
+            .map(|i| char::from_digit(self.digits[i], 10).unwrap())
+            .collect()
+    }
+    fn decrypt(&self, ciphertxt: &str) -> String {
+        let pairs = symbol_groups(2);
+        let morse: String = ciphertxt
+            .chars()
+            .filter_map(|c| c.to_digit(10))
+            .filter_map(|d| self.digits.iter().position(|&x| x == d))
+            .map(|i| pairs[i].as_str())
+            .collect();
+        decode(&morse)
+    }
+}
+
+#[derive(Debug)]
+pub struct Pollux {
+    key: String,
+    seed: Option<u64>,
+}
+impl Pollux {
+    pub fn new(key: &str, seed: Option<u64>) -> Result<Self, String> {
+        if key.chars().count() != 10 || !key.chars().all(|c| SYMBOLS.contains(c)) {
+            return Err(String::from("Key must assign one of '.-x' to each digit!"));
+        }
+        if !SYMBOLS.chars().all(|s| key.contains(s)) {
+            return Err(String::from("Key must use each of '.-x' at least once!"));
+        }
+        Ok(Self {
+            key: key.to_string(),
+            seed,
+        })
+    }
+}
+impl Cipher for Pollux {
+    fn encrypt(&self, plaintext: &str) -> String {
+        let mut rng = match self.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        encode(plaintext)
+            .chars()
+            .map(|s| {
+                let choices: Vec<char> = self
+                    .key
+                    .chars()
+                    .zip('0'..='9')
+                    .filter_map(|(k, d)| if k == s { Some(d) } else { None })
+                    .collect();
+                choices[rng.gen_range(0..choices.len())]
+            })
+            .collect()
+    }
+    fn decrypt(&self, ciphertxt: &str) -> String {
+        let morse: String = ciphertxt
+            .chars()
+            .filter_map(|c| c.to_digit(10))
+            .filter_map(|d| self.key.chars().nth(d as usize))
+            .collect();
+        decode(&morse)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::ENGLISH;
+
+    #[test]
+    fn morse() {
+        let morse = encode("SOS AT 9");
+        assert_eq!(morse, "...x---x...xx.-x-xx----.");
+        assert_eq!(decode(&morse), "SOS AT 9");
+    }
+
+    #[test]
+    fn fractionated() {
+        let plaintext = "COME AT ONCE";
+        let fm = FractionatedMorse::new(ENGLISH, "ROUNDTABLE").unwrap();
+        let ciphertxt = fm.encrypt(plaintext);
+        assert_eq!(ciphertxt, "CBIILTMHVVFL");
+        assert_eq!(fm.decrypt(&ciphertxt), plaintext);
+    }
+
+    #[test]
+    fn morbit() {
+        let plaintext = "ONCE UPON A TIME";
+        let morbit = Morbit::new(ENGLISH, "WISECRACK").unwrap();
+        assert_eq!(morbit.digits, [9, 5, 8, 4, 2, 7, 1, 3, 6]);
+        let ciphertxt = "27435881512827465679378";
+        assert_eq!(morbit.encrypt(plaintext), ciphertxt);
+        assert_eq!(morbit.decrypt(ciphertxt), plaintext);
+    }
+
+    #[test]
+    fn pollux() {
+        let plaintext = "LUCK HELPS";
+        let pollux = Pollux::new("x.-x-.x-.-", Some(42)).unwrap();
+        // the digits depend on StdRng's stream under rand 0.8
+        let ciphertxt = "575138823712509570358153861751352956851";
+        assert_eq!(pollux.encrypt(plaintext), ciphertxt);
+        assert_eq!(pollux.decrypt(ciphertxt), plaintext);
+        let symbols: String = ciphertxt
+            .chars()
+            .map(|d| {
+                pollux
+                    .key
+                    .chars()
+                    .nth(d.to_digit(10).unwrap() as usize)
+                    .unwrap()
+            })
+            .collect();
+        assert_eq!(symbols, encode(plaintext));
+        assert_eq!(encode("LU"), ".-..x..-");
+        assert!(Pollux::new("..........", None).is_err());
+    }
+}
@@ -0,0 +1,185 @@
+use crate::common::{alphabetize, char_index, check_unique, filter, refill, Cipher, ENGLISH};
+use crate::stats::{chi_squared, NGrams, Solution};
+use crate::transpose::key_order;
+use crate::vigenere::Vigenere;
+
+pub const BLOCK_ROWS: usize = 5;
+
+fn block_order(len: usize, n_cols: usize, order: &[usize]) -> Vec<usize> {
+    let mut positions = Vec::with_capacity(len);
+    for start in (0..len).step_by(BLOCK_ROWS * n_cols) {
+        let end = len.min(start + BLOCK_ROWS * n_cols);
+        for &col in order {
+            positions.extend((start + col..end).step_by(n_cols));
+        }
+    }
+    positions
+}
+
+fn permutations(n: usize) -> Vec<Vec<usize>> {
+    if n == 0 {
+        return vec![vec![]];
+    }
+    let mut output = Vec::new();
+    for perm in permutations(n - 1) {
+        for i in 0..n {
+            let mut next = perm.clone();
+            next.insert(i, n - 1);
+            output.push(next);
+        }
+    }
+    output
+}
+
+#[derive(Debug)]
+pub struct Nicodemus {
+    alphabet: String,
+    order: Vec<usize>,
+    vigenere: Vigenere,
+}
+impl Nicodemus {
+    pub fn new(alphabet: &str, keyword: &str) -> Result<Self, String> {
+        check_unique(alphabet).unwrap();
+        if keyword.is_empty() {
+            return Err(String::from("Keyword must not be empty!"));
+        }
+        Ok(Self {
+            alphabet: alphabet.to_string(),
+            order: key_order(keyword, alphabet),
+            vigenere: Vigenere::new(alphabet, keyword)?,
+        })
+    }
+
+    pub fn solve(alphabet: &str, ciphertxt: &str, max_len: usize) -> Solution {
+        let chars: Vec<char> = filter(ciphertxt, alphabet).chars().collect();
+        let indices = char_index(&filter(ciphertxt, alphabet), alphabet);
+        let alphalen = alphabet.chars().count() as u32;
+        let bigrams = NGrams::english(2);
+
+        let (mut best, mut best_plain) = (None, String::new());
+        for n_cols in 2..=max_len {
+            let n_blocks = chars.len() / (BLOCK_ROWS * n_cols);
+            if n_blocks == 0 {
+                break;
+            }
+
+            // in every full block, sorted column k is the k-th run of five letters
+            let letters: Vec<char> = (0..n_cols)
+                .map(|k| {
+                    let column: String = (0..n_blocks)
+                        .flat_map(|b| {
+                            let start = (b * n_cols + k) * BLOCK_ROWS;
+                            chars[start..start + BLOCK_ROWS].iter()
+                        })
+                        .collect();
+                    let shifts = alphabet.chars().map(|c| {
+                        let vigenere = Vigenere::new(alphabet, &c.to_string()).unwrap();
+                        (c, chi_squared(&vigenere.decrypt(&column)))
+                    });
+                    shifts.min_by(|a, b| a.1.total_cmp(&b.1)).unwrap().0
+                })
+                .collect();
+            let shifts = char_index(&letters.iter().collect::<String>(), alphabet);
+            if shifts.windows(2).any(|w| w[0] > w[1]) {
+                continue;
+            }
+
+            for order in permutations(n_cols) {
+                if (1..n_cols).any(|k| shifts[k - 1] == shifts[k] && order[k - 1] > order[k]) {
+                    continue;
+                }
+                let mut rank = vec![0; n_cols];
+                for (k, &col) in order.iter().enumerate() {
+                    rank[col] = k;
+                }
+                let mut plain = vec![0; indices.len()];
+                for (k, pos) in block_order(indices.len(), n_cols, &order)
+                    .into_iter()
+                    .enumerate()
+                {
+                    plain[pos] = (indices[k] + alphalen - shifts[rank[pos % n_cols]]) % alphalen;
+                }
+                let plaintext = alphabetize(plain, alphabet);
+                let score = bigrams.score(&plaintext) / chars.len() as f64;
+                if best.as_ref().is_none_or(|(_, s)| score > *s) {
+                    let keyword: String = rank.iter().map(|&k| letters[k]).collect();
+                    (best, best_plain) = (Some((keyword, score)), plaintext);
+                }
+            }
+        }
+        let (key, score) = best.unwrap_or((String::new(), f64::NEG_INFINITY));
+        Solution {
+            key,
+            score,
+            confidence: NGrams::english(4).confidence(&char_index(&best_plain, ENGLISH)),
+        }
+    }
+}
+impl Cipher for Nicodemus {
+    fn encrypt(&self, plaintext: &str) -> String {
+        let clean = filter(plaintext, &self.alphabet);
+        let indices = char_index(&self.vigenere.encrypt(&clean), &self.alphabet);
+        let transposed = block_order(indices.len(), self.order.len(), &self.order)
+            .into_iter()
+            .map(|pos| indices[pos])
+            .collect();
+        refill(
+            &alphabetize(transposed, &self.alphabet),
+            plaintext,
+            &self.alphabet,
+        )
+    }
+    fn decrypt(&self, ciphertxt: &str) -> String {
+        let indices = char_index(&filter(ciphertxt, &self.alphabet), &self.alphabet);
+        let mut untransposed = vec![0; indices.len()];
+        for (k, pos) in block_order(indices.len(), self.order.len(), &self.order)
+            .into_iter()
+            .enumerate()
+        {
+            untransposed[pos] = indices[k];
+        }
+        let output = self
+            .vigenere
+            .decrypt(&alphabetize(untransposed, &self.alphabet));
+        refill(&output, ciphertxt, &self.alphabet)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::KRYPTOS;
+
+    const EXAMPLE: &str = "THEEARLYBIRDGETSTHEWORMBUTTHESECONDMOUSEGETSTHECHEESE";
+
+    #[test]
+    fn nicodemus() {
+        let nicodemus = Nicodemus::new(ENGLISH, "CAT").unwrap();
+        let ciphertxt = "HAYREVGNKIXKUWMTWMTEUGTWJAHUMLCDUGSGPQGVHFLXMEEEJJUVX";
+        assert_eq!(nicodemus.encrypt(EXAMPLE), ciphertxt);
+        assert_eq!(nicodemus.decrypt(ciphertxt), EXAMPLE);
+        assert_eq!(nicodemus.encrypt("ABC DEF"), "BEC FVY");
+        assert_eq!(nicodemus.decrypt("BEC FVY"), "ABC DEF");
+    }
+
+    #[test]
+    fn blocks() {
+        let positions = block_order(17, 3, &[1, 0, 2]);
+        assert_eq!(positions[..5], [1, 4, 7, 10, 13]);
+        assert_eq!(positions[15..], [16, 15]);
+
+        let plaintext = include_str!("kryptos/k1_plain.txt").trim();
+        let nicodemus = Nicodemus::new(KRYPTOS, "PALIMPSEST").unwrap();
+        let ciphertxt = nicodemus.encrypt(plaintext);
+        assert_eq!(nicodemus.decrypt(&ciphertxt), plaintext);
+    }
+
+    #[test]
+    fn solve() {
+        let ciphertxt = Nicodemus::new(ENGLISH, "CAT").unwrap().encrypt(EXAMPLE);
+        let solution = Nicodemus::solve(ENGLISH, &ciphertxt, 5);
+        assert_eq!(solution.key, "CAT");
+        assert!(solution.confidence > 0.5);
+        assert_eq!(Nicodemus::solve(ENGLISH, "TOOSHORT", 5).key, "");
+    }
+}
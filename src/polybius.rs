@@ -0,0 +1,145 @@
+use crate::common::{check_unique, filter, keyed_alphabet, refill, Cipher};
+
+pub const POLYBIUS: &str = "ABCDEFGHIKLMNOPQRSTUVWXYZ";
+
+pub fn merge_ij(input: &str, alphabet: &str) -> String {
+    match alphabet.contains('J') {
+        false => input.replace('J', "I"),
+        true => input.to_string(),
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Square {
+    alphabet: String,
+    size: usize,
+}
+impl Square {
+    pub fn new(alphabet: &str, keyword: &str) -> Result<Self, String> {
+        check_unique(alphabet).unwrap();
+        let keyword = merge_ij(keyword, alphabet);
+        let alphabet = keyed_alphabet(&keyword, alphabet);
+        let len = alphabet.chars().count();
+        let size = (len as f64).sqrt() as usize;
+        if size * size != len {
+            return Err(String::from("Alphabet length must be a perfect square!"));
+        }
+        Ok(Self { alphabet, size })
+    }
+
+    pub fn alphabet(&self) -> &str {
+        &self.alphabet
+    }
+    pub fn size(&self) -> usize {
+        self.size
+    }
+    pub fn locate(&self, c: char) -> Option<(usize, usize)> {
+        let idx = self.alphabet.chars().position(|x| x == c)?;
+        Some((idx / self.size, idx % self.size))
+    }
+    pub fn at(&self, row: usize, col: usize) -> char {
+        let idx = (row % self.size) * self.size + col % self.size;
+        self.alphabet.chars().nth(idx).unwrap()
+    }
+}
+
+pub const PHILLIPS_SQUARES: usize = 8;
+pub const PHILLIPS_GROUP: usize = 5;
+
+#[derive(Debug)]
+pub struct Phillips {
+    squares: Vec<Square>,
+}
+impl Phillips {
+    pub fn new(alphabet: &str, keyword: &str) -> Result<Self, String> {
+        let keyed = Square::new(alphabet, keyword)?;
+        let rows: Vec<String> = keyed
+            .alphabet()
+            .chars()
+            .collect::<Vec<char>>()
+            .chunks(keyed.size())
+            .map(|row| row.iter().collect())
+            .collect();
+
+        let mut order: Vec<usize> = (0..rows.len()).collect();
+        let mut squares = vec![keyed.clone()];
+        let mut bottom = rows.len();
+        while squares.len() < PHILLIPS_SQUARES && bottom > 1 {
+            for i in 1..bottom {
+                order.swap(i - 1, i);
+                let alphabet: String = order.iter().map(|&r| rows[r].as_str()).collect();
+                squares.push(Square::new(&alphabet, "")?);
+                if squares.len() == PHILLIPS_SQUARES {
+                    break;
+                }
+            }
+            bottom -= 1;
+        }
+        Ok(Self { squares })
+    }
+
+    fn substitute(&self, input: &str, decrypt: bool) -> String {
+        let input = merge_ij(input, self.squares[0].alphabet());
+        let alphabet = self.squares[0].alphabet();
+        let size = self.squares[0].size();
+        let step = match decrypt {
+            false => 1,
+            true => size - 1,
+        };
+        let output: String = filter(&input, alphabet)
+            .chars()
+            .enumerate()
+            .map(|(i, c)| {
+                let square = &self.squares[(i / PHILLIPS_GROUP) % self.squares.len()];
+                let (row, col) = square.locate(c).unwrap();
+                square.at(row + step, col + step)
+            })
+            .collect();
+        refill(&output, &input, alphabet)
+    }
+}
+impl Cipher for Phillips {
+    fn encrypt(&self, plaintext: &str) -> String {
+        self.substitute(plaintext, false)
+    }
+    fn decrypt(&self, ciphertxt: &str) -> String {
+        self.substitute(ciphertxt, true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::ENGLISH;
+
+    #[test]
+    fn square() {
+        let square = Square::new(POLYBIUS, "KRYPTOS").unwrap();
+        assert_eq!(square.alphabet(), "KRYPTOSABCDEFGHILMNQUVWXZ");
+        assert_eq!(square.locate('A'), Some((1, 2)));
+        assert_eq!(square.at(1, 2), 'A');
+        assert_eq!(square.locate('J'), None);
+        assert!(Square::new(ENGLISH, "KRYPTOS").is_err());
+    }
+
+    #[test]
+    fn phillips() {
+        let phillips = Phillips::new(POLYBIUS, "DIAGONAL").unwrap();
+        assert_eq!(phillips.encrypt("AZBBB A"), "CDMMM M");
+        assert_eq!(phillips.decrypt("CDMMM M"), "AZBBB A");
+
+        // computed from the ACA rules with a separate model, not the published ACA example
+        let plaintext = "SQUARES ONE AND FIVE ARE THE SAME ONLY IN NAME, ALL EIGHT DIFFER";
+        let ciphertxt = "YWVCXDY FID THR ISLF YAF ZSF YCEQ FRSO KW WMUD, MAA FBESZ HKRRDX";
+        assert_eq!(phillips.encrypt(plaintext), ciphertxt);
+        assert_eq!(phillips.decrypt(ciphertxt), plaintext);
+
+        // squares 1 and 5, and 2 and 8, differ only by a cyclic shift of rows
+        let groups = phillips.encrypt(&"PHILL".repeat(8));
+        let group = |n: usize| &groups[5 * (n - 1)..5 * n];
+        assert_eq!(group(1), group(5));
+        assert_eq!(group(2), group(8));
+        assert_ne!(group(1), group(2));
+        assert_eq!(phillips.decrypt(&phillips.encrypt("JOIN")), "IOIN");
+    }
+}
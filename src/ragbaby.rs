@@ -0,0 +1,86 @@
+use crate::common::{check_unique, keyed_alphabet, Cipher};
+
+// J is enciphered as I and X as W
+pub const RAGBABY: &str = "ABCDEFGHIKLMNOPQRSTUVWYZ";
+
+fn merge(input: &str, alphabet: &str) -> String {
+    input
+        .chars()
+        .map(|c| match c {
+            'J' if !alphabet.contains('J') => 'I',
+            'X' if !alphabet.contains('X') => 'W',
+            _ => c,
+        })
+        .collect()
+}
+
+#[derive(Debug)]
+pub struct Ragbaby {
+    keyed: Vec<char>,
+}
+impl Ragbaby {
+    pub fn new(alphabet: &str, keyword: &str) -> Result<Self, String> {
+        check_unique(alphabet).unwrap();
+        let keyword = merge(keyword, alphabet);
+        if keyword.chars().any(|c| !alphabet.contains(c)) {
+            return Err(String::from("Keyword contains invalid characters!"));
+        }
+        Ok(Self {
+            keyed: keyed_alphabet(&keyword, alphabet).chars().collect(),
+        })
+    }
+
+    // shift by word number (from 1) plus position in the word (from 0)
+    fn substitute(&self, input: &str, decrypt: bool) -> String {
+        let n = self.keyed.len();
+        let (mut word, mut pos) = (1, 0);
+        let mut output = String::with_capacity(input.len());
+        for c in merge(input, &self.keyed.iter().collect::<String>()).chars() {
+            let Some(i) = self.keyed.iter().position(|&x| x == c) else {
+                if pos > 0 {
+                    (word, pos) = (word + 1, 0);
+                }
+                output.push(c);
+                continue;
+            };
+            let shift = (word + pos) % n;
+            output.push(match decrypt {
+                false => self.keyed[(i + shift) % n],
+                true => self.keyed[(i + n - shift) % n],
+            });
+            pos += 1;
+        }
+        output
+    }
+}
+impl Cipher for Ragbaby {
+    fn encrypt(&self, plaintext: &str) -> String {
+        self.substitute(plaintext, false)
+    }
+    fn decrypt(&self, ciphertxt: &str) -> String {
+        self.substitute(ciphertxt, true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::ENGLISH;
+
+    #[test]
+    fn grosbeak() {
+        let ragbaby = Ragbaby::new(RAGBABY, "GROSBEAK").unwrap();
+        let ciphertxt = ragbaby.encrypt("WORD DIVISIONS ARE KEPT.");
+        assert_eq!(ciphertxt, "YBBL HNGQDUFGL DEF HFYR.");
+        assert_eq!(ragbaby.decrypt(&ciphertxt), "WORD DIVISIONS ARE KEPT.");
+        assert_eq!(ragbaby.decrypt(&ragbaby.encrypt("JINX")), "IINW");
+    }
+
+    #[test]
+    fn alphabets() {
+        let ragbaby = Ragbaby::new(ENGLISH, "ZEBRAS").unwrap();
+        let plaintext = "THE QUICK BROWN FOX JUMPS, OVER THE LAZY DOG";
+        assert_eq!(ragbaby.decrypt(&ragbaby.encrypt(plaintext)), plaintext);
+        assert!(Ragbaby::new(RAGBABY, "KEY1").is_err());
+    }
+}
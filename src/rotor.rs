@@ -0,0 +1,588 @@
+use crate::common::{check_unique, filter, refill, Cipher, ENGLISH};
+use std::fmt::Debug;
+
+fn permutation(alphabet: &str, wiring: &str) -> Result<Vec<usize>, String> {
+    let chars: Vec<char> = alphabet.chars().collect();
+    let map = wiring
+        .chars()
+        .map(|c| chars.iter().position(|&x| x == c))
+        .collect::<Option<Vec<usize>>>();
+    match map {
+        Some(map) if map.len() == chars.len() && (0..chars.len()).all(|i| map.contains(&i)) => {
+            Ok(map)
+        }
+        _ => Err(format!(
+            "Wiring {} is not a permutation of the alphabet!",
+            wiring
+        )),
+    }
+}
+
+fn invert(map: &[usize]) -> Vec<usize> {
+    let mut inverse = vec![0; map.len()];
+    for (i, &j) in map.iter().enumerate() {
+        inverse[j] = i;
+    }
+    inverse
+}
+
+fn indices(input: &str, alphabet: &str) -> Result<Vec<usize>, String> {
+    input
+        .chars()
+        .map(|c| alphabet.chars().position(|x| x == c))
+        .collect::<Option<Vec<usize>>>()
+        .ok_or(format!("{} contains invalid characters!", input))
+}
+
+#[derive(Debug, Clone)]
+pub struct Rotor {
+    forward: Vec<usize>,
+    backward: Vec<usize>,
+    notches: Vec<usize>,
+    ring: usize,
+    position: usize,
+}
+impl Rotor {
+    pub fn new(alphabet: &str, wiring: &str, notches: &str) -> Result<Self, String> {
+        check_unique(alphabet).unwrap();
+        let forward = permutation(alphabet, wiring)?;
+        Ok(Self {
+            backward: invert(&forward),
+            forward,
+            notches: indices(notches, alphabet)?,
+            ring: 0,
+            position: 0,
+        })
+    }
+    // as on Typex, only the core is reversed; notches, ring and position stay on the tyre
+    pub fn reversed(&self) -> Self {
+        let n = self.forward.len();
+        let mirror = |i: usize| (n - i) % n;
+        let forward: Vec<usize> = (0..n).map(|i| mirror(self.backward[mirror(i)])).collect();
+        Self {
+            backward: invert(&forward),
+            forward,
+            ..self.clone()
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.forward.len()
+    }
+    pub fn position(&self) -> usize {
+        self.position
+    }
+    pub fn set_position(&mut self, position: usize) {
+        self.position = position % self.size();
+    }
+    pub fn set_ring(&mut self, ring: usize) {
+        self.ring = ring % self.size();
+    }
+    pub fn at_notch(&self) -> bool {
+        self.notches.contains(&self.position)
+    }
+    pub fn step(&mut self) {
+        self.position = (self.position + 1) % self.size();
+    }
+
+    fn through(&self, map: &[usize], c: usize) -> usize {
+        let n = self.size();
+        let shift = (self.position + n - self.ring) % n;
+        (map[(c + shift) % n] + n - shift) % n
+    }
+    pub fn forward(&self, c: usize) -> usize {
+        self.through(&self.forward, c)
+    }
+    pub fn backward(&self, c: usize) -> usize {
+        self.through(&self.backward, c)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Stator {
+    forward: Vec<usize>,
+    backward: Vec<usize>,
+}
+impl Stator {
+    pub fn new(alphabet: &str, wiring: &str) -> Result<Self, String> {
+        check_unique(alphabet).unwrap();
+        let forward = permutation(alphabet, wiring)?;
+        Ok(Self {
+            backward: invert(&forward),
+            forward,
+        })
+    }
+    pub fn identity(size: usize) -> Self {
+        Self {
+            forward: (0..size).collect(),
+            backward: (0..size).collect(),
+        }
+    }
+    pub fn from_rotor(rotor: &Rotor) -> Self {
+        let forward: Vec<usize> = (0..rotor.size()).map(|c| rotor.forward(c)).collect();
+        Self {
+            backward: invert(&forward),
+            forward,
+        }
+    }
+
+    pub fn forward(&self, c: usize) -> usize {
+        self.forward[c]
+    }
+    pub fn backward(&self, c: usize) -> usize {
+        self.backward[c]
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Reflector {
+    wiring: Vec<usize>,
+}
+impl Reflector {
+    pub fn new(alphabet: &str, wiring: &str) -> Result<Self, String> {
+        check_unique(alphabet).unwrap();
+        let wiring = permutation(alphabet, wiring)?;
+        if (0..wiring.len()).any(|i| wiring[wiring[i]] != i) {
+            return Err(String::from("Reflector must pair up its contacts!"));
+        }
+        Ok(Self { wiring })
+    }
+    pub fn from_pairs(alphabet: &str, pairs: &str) -> Result<Self, String> {
+        let wiring = Plugboard::new(alphabet, pairs)?.wiring;
+        Ok(Self { wiring })
+    }
+
+    pub fn reflect(&self, c: usize) -> usize {
+        self.wiring[c]
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Plugboard {
+    wiring: Vec<usize>,
+}
+impl Plugboard {
+    pub fn new(alphabet: &str, pairs: &str) -> Result<Self, String> {
+        check_unique(alphabet).unwrap();
+        let mut wiring: Vec<usize> = (0..alphabet.chars().count()).collect();
+        for pair in pairs.split_whitespace() {
+            match indices(pair, alphabet)?[..] {
+                [a, b] if a != b && wiring[a] == a && wiring[b] == b => wiring.swap(a, b),
+                _ => return Err(format!("Invalid plugboard pair: {}", pair)),
+            }
+        }
+        Ok(Self { wiring })
+    }
+
+    pub fn swap(&self, c: usize) -> usize {
+        self.wiring[c]
+    }
+}
+
+// each message starts from a copy, so strategies may keep state
+pub trait Stepping: Debug {
+    fn step(&mut self, rotors: &mut [Rotor]);
+    fn boxed(&self) -> Box<dyn Stepping>;
+}
+
+#[derive(Debug, Clone)]
+pub struct Odometer;
+impl Stepping for Odometer {
+    fn step(&mut self, rotors: &mut [Rotor]) {
+        for rotor in rotors.iter_mut().rev() {
+            let carry = rotor.at_notch();
+            rotor.step();
+            if !carry {
+                break;
+            }
+        }
+    }
+    fn boxed(&self) -> Box<dyn Stepping> {
+        Box::new(self.clone())
+    }
+}
+
+// double stepping: a middle rotor at its notch moves with the left one
+#[derive(Debug, Clone)]
+pub struct DoubleStep;
+impl Stepping for DoubleStep {
+    fn step(&mut self, rotors: &mut [Rotor]) {
+        let n = rotors.len();
+        if n < 3 {
+            return Odometer.step(rotors);
+        }
+        let (left, middle, right) = (n - 3, n - 2, n - 1);
+        if rotors[middle].at_notch() {
+            rotors[middle].step();
+            rotors[left].step();
+        } else if rotors[right].at_notch() {
+            rotors[middle].step();
+        }
+        rotors[right].step();
+    }
+    fn boxed(&self) -> Box<dyn Stepping> {
+        Box::new(self.clone())
+    }
+}
+
+// the control bank picks which cipher rotors move, then steps like an odometer
+#[derive(Debug, Clone)]
+pub struct Control {
+    rotors: Vec<Rotor>,
+    live: Vec<usize>,
+}
+impl Control {
+    pub fn new(alphabet: &str, rotors: Vec<Rotor>, live: &str) -> Result<Self, String> {
+        check_unique(alphabet).unwrap();
+        let live = indices(live, alphabet)?;
+        if rotors.is_empty() || live.is_empty() {
+            return Err(String::from(
+                "Control stepping needs control rotors and live contacts!",
+            ));
+        }
+        Ok(Self { rotors, live })
+    }
+}
+impl Stepping for Control {
+    fn step(&mut self, rotors: &mut [Rotor]) {
+        let mut moves = vec![false; rotors.len()];
+        for &c in &self.live {
+            let out = self.rotors.iter().rev().fold(c, |c, r| r.forward(c));
+            moves[out % rotors.len()] = true;
+        }
+        for (rotor, _) in rotors.iter_mut().zip(moves).filter(|(_, moves)| *moves) {
+            rotor.step();
+        }
+        Odometer.step(&mut self.rotors);
+    }
+    fn boxed(&self) -> Box<dyn Stepping> {
+        Box::new(self.clone())
+    }
+}
+
+pub fn stepping(name: &str) -> Result<Box<dyn Stepping>, String> {
+    match name.to_lowercase().as_str() {
+        "odometer" => Ok(Box::new(Odometer)),
+        "enigma" | "double" => Ok(Box::new(DoubleStep)),
+        _ => Err(format!("Unknown stepping: {}", name)),
+    }
+}
+
+// without a reflector, decryption runs the path in reverse
+#[derive(Debug)]
+pub struct Machine {
+    alphabet: String,
+    plugboard: Plugboard,
+    entry: Stator,
+    rotors: Vec<Rotor>,
+    stators: Vec<Stator>,
+    reflector: Option<Reflector>,
+    stepping: Box<dyn Stepping>,
+}
+impl Machine {
+    pub fn new(
+        alphabet: &str,
+        plugboard: Plugboard,
+        entry: Stator,
+        rotors: Vec<Rotor>,
+        stators: Vec<Stator>,
+        reflector: Option<Reflector>,
+        stepping: Box<dyn Stepping>,
+    ) -> Result<Self, String> {
+        check_unique(alphabet).unwrap();
+        let n = alphabet.chars().count();
+        let sizes = [plugboard.wiring.len(), entry.forward.len()]
+            .into_iter()
+            .chain(rotors.iter().map(|r| r.size()))
+            .chain(stators.iter().map(|s| s.forward.len()))
+            .chain(reflector.iter().map(|r| r.wiring.len()));
+        if rotors.is_empty() || sizes.into_iter().any(|size| size != n) {
+            return Err(String::from(
+                "Machine needs rotors, all wired for the alphabet!",
+            ));
+        }
+        Ok(Self {
+            alphabet: alphabet.to_string(),
+            plugboard,
+            entry,
+            rotors,
+            stators,
+            reflector,
+            stepping,
+        })
+    }
+
+    pub fn from_config(config: &str) -> Result<Self, String> {
+        let mut entries: Vec<(String, String)> = Vec::new();
+        for line in config.lines() {
+            let line = line.split('#').next().unwrap().trim();
+            if let Some((name, value)) = line.split_once(':') {
+                entries.push((name.trim().to_lowercase(), value.trim().to_string()));
+            }
+        }
+        let get = |name: &str| {
+            entries
+                .iter()
+                .rev()
+                .find(|(n, _)| n == name)
+                .map(|(_, v)| v.as_str())
+        };
+        let alphabet = get("alphabet").unwrap_or(ENGLISH);
+        check_unique(alphabet)?;
+        let n = alphabet.chars().count();
+
+        // rings and positions follow the file order of rotor and stator lines, but the
+        // stators always sit on the entry side of the moving rotors
+        let mut wheels = Vec::new();
+        let mut moving = Vec::new();
+        let mut control = Vec::new();
+        for (name, value) in &entries {
+            match name.as_str() {
+                "rotor" | "stator" | "control" => {
+                    let mut parts: Vec<&str> = value.split_whitespace().collect();
+                    let reversed = parts.last() == Some(&"reversed");
+                    if reversed {
+                        parts.pop();
+                    }
+                    let rotor = match parts[..] {
+                        [wiring] => Rotor::new(alphabet, wiring, "")?,
+                        [wiring, notches] => Rotor::new(alphabet, wiring, notches)?,
+                        _ => return Err(format!("Invalid {}: {}", name, value)),
+                    };
+                    let rotor = if reversed { rotor.reversed() } else { rotor };
+                    match name.as_str() {
+                        "control" => control.push(rotor),
+                        _ => {
+                            wheels.push(rotor);
+                            moving.push(name == "rotor");
+                        }
+                    }
+                }
+                "alphabet" | "stepping" | "entry" | "plugboard" | "reflector" | "rings"
+                | "positions" | "live" => {}
+                _ => return Err(format!("Unknown config entry: {}", name)),
+            }
+        }
+        for (setting, values) in [("rings", get("rings")), ("positions", get("positions"))] {
+            let Some(values) = values else {
+                continue;
+            };
+            let values = indices(&values.replace(' ', ""), alphabet)?;
+            if values.len() != wheels.len() {
+                return Err(format!("Expected {} {}!", wheels.len(), setting));
+            }
+            for (wheel, value) in wheels.iter_mut().zip(values) {
+                match setting {
+                    "rings" => wheel.set_ring(value),
+                    _ => wheel.set_position(value),
+                }
+            }
+        }
+        let control_stepping = get("stepping").is_some_and(|s| s.eq_ignore_ascii_case("control"));
+        if !control.is_empty() && !control_stepping {
+            return Err(String::from("Control rotors need stepping: control!"));
+        }
+
+        let (rotors, stators): (Vec<_>, Vec<_>) = wheels
+            .into_iter()
+            .zip(moving)
+            .partition(|(_, moving)| *moving);
+        Self::new(
+            alphabet,
+            Plugboard::new(alphabet, get("plugboard").unwrap_or(""))?,
+            match get("entry") {
+                Some(wiring) => Stator::new(alphabet, wiring)?,
+                None => Stator::identity(n),
+            },
+            rotors.into_iter().map(|(rotor, _)| rotor).collect(),
+            stators
+                .iter()
+                .map(|(stator, _)| Stator::from_rotor(stator))
+                .collect(),
+            get("reflector")
+                .map(|wiring| Reflector::new(alphabet, wiring))
+                .transpose()?,
+            match control_stepping {
+                true => Box::new(Control::new(alphabet, control, get("live").unwrap_or(""))?),
+                false => stepping(get("stepping").unwrap_or("odometer"))?,
+            },
+        )
+    }
+
+    pub fn rotors(&self) -> &[Rotor] {
+        &self.rotors
+    }
+    pub fn positions(&self) -> String {
+        self.rotors
+            .iter()
+            .map(|r| self.alphabet.chars().nth(r.position()).unwrap())
+            .collect()
+    }
+    pub fn set_positions(&mut self, positions: &str) -> Result<(), String> {
+        let positions = indices(positions, &self.alphabet)?;
+        if positions.len() != self.rotors.len() {
+            return Err(format!("Expected {} positions!", self.rotors.len()));
+        }
+        for (rotor, position) in self.rotors.iter_mut().zip(positions) {
+            rotor.set_position(position);
+        }
+        Ok(())
+    }
+
+    fn encipher(&self, rotors: &[Rotor], c: usize) -> usize {
+        let mut c = self.entry.forward(self.plugboard.swap(c));
+        c = self.stators.iter().rev().fold(c, |c, s| s.forward(c));
+        c = rotors.iter().rev().fold(c, |c, r| r.forward(c));
+        let Some(reflector) = &self.reflector else {
+            return c;
+        };
+        c = rotors
+            .iter()
+            .fold(reflector.reflect(c), |c, r| r.backward(c));
+        c = self.stators.iter().fold(c, |c, s| s.backward(c));
+        self.plugboard.swap(self.entry.backward(c))
+    }
+    fn decipher(&self, rotors: &[Rotor], c: usize) -> usize {
+        if self.reflector.is_some() {
+            return self.encipher(rotors, c);
+        }
+        let c = rotors.iter().fold(c, |c, r| r.backward(c));
+        let c = self.stators.iter().fold(c, |c, s| s.backward(c));
+        self.plugboard.swap(self.entry.backward(c))
+    }
+
+    fn substitute(&self, input: &str, decrypt: bool) -> String {
+        let chars: Vec<char> = self.alphabet.chars().collect();
+        let (mut rotors, mut stepping) = (self.rotors.clone(), self.stepping.boxed());
+        let output: String = filter(input, &self.alphabet)
+            .chars()
+            .map(|c| {
+                stepping.step(&mut rotors);
+                let c = chars.iter().position(|&x| x == c).unwrap();
+                match decrypt {
+                    false => chars[self.encipher(&rotors, c)],
+                    true => chars[self.decipher(&rotors, c)],
+                }
+            })
+            .collect();
+        refill(&output, input, &self.alphabet)
+    }
+}
+impl Cipher for Machine {
+    fn encrypt(&self, plaintext: &str) -> String {
+        self.substitute(plaintext, false)
+    }
+    fn decrypt(&self, ciphertxt: &str) -> String {
+        self.substitute(ciphertxt, true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ENIGMA_I: &str = include_str!("rotor/enigma_i.txt");
+
+    #[test]
+    fn enigma() {
+        let mut enigma = Machine::from_config(ENIGMA_I).unwrap();
+        assert_eq!(enigma.encrypt("AAAAA"), "BDZGO");
+        assert_eq!(enigma.decrypt("BDZGO"), "AAAAA");
+
+        enigma.set_positions("ADU").unwrap();
+        let mut rotors = enigma.rotors().to_vec();
+        let positions: Vec<Vec<usize>> = (0..3)
+            .map(|_| {
+                DoubleStep.step(&mut rotors);
+                rotors.iter().map(|r| r.position()).collect()
+            })
+            .collect();
+        assert_eq!(positions, [[0, 3, 21], [0, 4, 22], [1, 5, 23]]);
+    }
+
+    #[test]
+    fn config() {
+        let config = ENIGMA_I
+            .replace("rings: AAA", "rings: BBB")
+            .replace("plugboard:", "plugboard: AZ BY");
+        let enigma = Machine::from_config(&config).unwrap();
+        let ciphertxt = enigma.encrypt("ROTOR MACHINES");
+        assert_ne!(
+            ciphertxt,
+            Machine::from_config(ENIGMA_I)
+                .unwrap()
+                .encrypt("ROTOR MACHINES")
+        );
+        assert_eq!(enigma.encrypt(&ciphertxt), "ROTOR MACHINES");
+
+        assert!(Machine::from_config(&ENIGMA_I.replace("AAA", "AA")).is_err());
+        assert!(Machine::from_config(&config.replace("BY", "AY")).is_err());
+        assert!(Machine::from_config("rotor: ABC").is_err());
+        assert!(Machine::from_config("cog: ABC").is_err());
+    }
+
+    #[test]
+    fn custom() {
+        let config = "alphabet: ABCDEF
+            stepping: odometer
+            stator: BADCFE
+            rotor: CAEBFD B
+            rotor: FEDCBA AD reversed
+            positions: CBA";
+        let machine = Machine::from_config(config).unwrap();
+        let ciphertxt = machine.encrypt("FACADE BEADED");
+        assert_eq!(machine.decrypt(&ciphertxt), "FACADE BEADED");
+        // the stator takes the first position, as it comes first in the file
+        assert_eq!(machine.positions(), "BA");
+
+        let rotor = Rotor::new(ENGLISH, "EKMFLGDQVZNTOWYHXUSPAIBRCJ", "Q").unwrap();
+        let twice = rotor.reversed().reversed();
+        assert!((0..26).all(|c| twice.forward(c) == rotor.forward(c)));
+    }
+
+    #[test]
+    fn control() {
+        // five cipher rotors without a reflector, moved by a bank of three control rotors
+        let config = "stepping: control
+            rotor: YCHLQSUGBDIXNZKERPVJTAWFOM A
+            rotor: INPXBWETGUYSAOCHVLDMQKZJFR A
+            rotor: WNDRIOZPTAXHFJYQBMSVEKUCGL A
+            rotor: TZGHOBKRVUXLQDMPNFWCJYEIAS A
+            rotor: YWTAHRQJVLCEXUNGBIPZMSDFOK A
+            control: QSLRBTEKOGAICFWYVMHJNXZUDP A
+            control: CHJDQIGNBSAKVTUOXFWLEPRMZY A
+            control: CDFAJXTIMNBEQHSUGRYLWZKVPO A
+            live: FGHI";
+        let machine = Machine::from_config(config).unwrap();
+        let ciphertxt = machine.encrypt("SIGABA STEPS ITS ROTORS IRREGULARLY");
+        assert_eq!(
+            machine.encrypt("SIGABA STEPS ITS ROTORS IRREGULARLY"),
+            ciphertxt
+        );
+        assert_eq!(
+            machine.decrypt(&ciphertxt),
+            "SIGABA STEPS ITS ROTORS IRREGULARLY"
+        );
+
+        let control = ["QSLRBTEKOGAICFWYVMHJNXZUDP", "CHJDQIGNBSAKVTUOXFWLEPRMZY"]
+            .map(|wiring| Rotor::new(ENGLISH, wiring, "A").unwrap());
+        let mut stepping = Control::new(ENGLISH, control.to_vec(), "FGHI").unwrap();
+        let mut rotors = machine.rotors().to_vec();
+        let mut moved = Vec::new();
+        for _ in 0..26 {
+            let before: Vec<usize> = rotors.iter().map(|r| r.position()).collect();
+            stepping.step(&mut rotors);
+            moved.push(
+                (0..5)
+                    .filter(|&i| rotors[i].position() != before[i])
+                    .count(),
+            );
+        }
+        // every letter moves at least one rotor, but not always the same number
+        assert!(moved.iter().all(|&n| (1..=4).contains(&n)));
+        assert!(moved.iter().any(|&n| n != moved[0]));
+        assert!(Control::new(ENGLISH, control.to_vec(), "").is_err());
+        assert!(Machine::from_config(&config.replace("live: FGHI", "")).is_err());
+        assert!(Machine::from_config(&config.replace("stepping: control", "")).is_err());
+    }
+}
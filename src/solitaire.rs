@@ -0,0 +1,177 @@
+use crate::common::{alphabetize, char_index, filter, Cipher, ENGLISH};
+use crate::vigenere::Vigenere;
+
+pub const DECK_SIZE: usize = 54;
+const JOKER_A: u8 = 53;
+const JOKER_B: u8 = 54;
+
+fn value(card: u8) -> usize {
+    card.min(JOKER_A) as usize
+}
+
+#[derive(Debug, Clone)]
+pub struct Solitaire {
+    deck: Vec<u8>,
+}
+impl Solitaire {
+    // bridge order, then jokers A and B
+    pub fn new(passphrase: &str) -> Self {
+        let mut solitaire = Self {
+            deck: (1..=DECK_SIZE as u8).collect(),
+        };
+        for i in char_index(&filter(&passphrase.to_uppercase(), ENGLISH), ENGLISH) {
+            solitaire.step();
+            solitaire.count_cut(i as usize + 1);
+        }
+        solitaire
+    }
+    // 1 to 52, A and B for the jokers
+    pub fn from_deck(deck: &str) -> Result<Self, String> {
+        let deck = deck
+            .split_whitespace()
+            .map(|card| match card {
+                "A" => Some(JOKER_A),
+                "B" => Some(JOKER_B),
+                _ => card.parse().ok().filter(|&c| (1..JOKER_A).contains(&c)),
+            })
+            .collect::<Option<Vec<u8>>>()
+            .ok_or(String::from("Deck contains invalid cards!"))?;
+        if deck.len() != DECK_SIZE || (1..=DECK_SIZE as u8).any(|c| !deck.contains(&c)) {
+            return Err(format!(
+                "Deck must hold each of the {} cards once!",
+                DECK_SIZE
+            ));
+        }
+        Ok(Self { deck })
+    }
+    pub fn deck(&self) -> String {
+        let cards: Vec<String> = self
+            .deck
+            .iter()
+            .map(|&card| match card {
+                JOKER_A => String::from("A"),
+                JOKER_B => String::from("B"),
+                _ => card.to_string(),
+            })
+            .collect();
+        cards.join(" ")
+    }
+
+    // wraps from the bottom to just below the top card
+    fn move_down(&mut self, card: u8, n: usize) {
+        for _ in 0..n {
+            let i = self.deck.iter().position(|&c| c == card).unwrap();
+            match i == DECK_SIZE - 1 {
+                true => {
+                    self.deck.remove(i);
+                    self.deck.insert(1, card);
+                }
+                false => self.deck.swap(i, i + 1),
+            }
+        }
+    }
+    fn triple_cut(&mut self) {
+        let a = self.deck.iter().position(|&c| c == JOKER_A).unwrap();
+        let b = self.deck.iter().position(|&c| c == JOKER_B).unwrap();
+        let (first, last) = (a.min(b), a.max(b));
+        self.deck = [
+            &self.deck[last + 1..],
+            &self.deck[first..=last],
+            &self.deck[..first],
+        ]
+        .concat();
+    }
+    fn count_cut(&mut self, n: usize) {
+        let bottom = self.deck.pop().unwrap();
+        self.deck.rotate_left(n.min(DECK_SIZE - 1));
+        self.deck.push(bottom);
+    }
+    fn step(&mut self) {
+        self.move_down(JOKER_A, 1);
+        self.move_down(JOKER_B, 2);
+        self.triple_cut();
+        self.count_cut(value(self.deck[DECK_SIZE - 1]));
+    }
+
+    pub fn advance(&mut self, n: usize) -> Vec<usize> {
+        let mut output = Vec::with_capacity(n);
+        while output.len() < n {
+            self.step();
+            let card = self.deck[value(self.deck[0])];
+            if card < JOKER_A {
+                output.push(card as usize);
+            }
+        }
+        output
+    }
+    pub fn keystream(&self, n: usize) -> String {
+        let values = self.clone().advance(n);
+        let indices = values.into_iter().map(|v| (v % 26) as u32).collect();
+        alphabetize(indices, ENGLISH)
+    }
+
+    fn substitute(&self, input: &str, decrypt: bool) -> String {
+        let n = filter(input, ENGLISH).len();
+        if n == 0 {
+            return input.to_string();
+        }
+        let vigenere = Vigenere::new(ENGLISH, &self.keystream(n)).unwrap();
+        match decrypt {
+            false => vigenere.encrypt(input),
+            true => vigenere.decrypt(input),
+        }
+    }
+}
+impl Cipher for Solitaire {
+    fn encrypt(&self, plaintext: &str) -> String {
+        self.substitute(plaintext, false)
+    }
+    fn decrypt(&self, ciphertxt: &str) -> String {
+        self.substitute(ciphertxt, true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unkeyed() {
+        let mut solitaire = Solitaire::new("");
+        assert_eq!(solitaire.encrypt("AAAAAAAAAAAAAAA"), "EXKYIZSGEHUNTIQ");
+        assert_eq!(solitaire.advance(10), [4, 49, 10, 24, 8, 51, 44, 6, 4, 33]);
+        assert_eq!(solitaire.encrypt("no letters: 42!"), "no letters: 42!");
+    }
+
+    #[test]
+    fn passphrase() {
+        let vectors = [
+            ("f", "XYIUQBMHKKJBEGY"),
+            ("fo", "TUJYMBERLGXNDIW"),
+            ("foo", "ITHZUJIWGRFARMW"),
+            ("a", "XODALGSCULIQNSC"),
+            ("aa", "OHGWMXXCAIMCIQP"),
+            ("aaa", "DCSQYHBQZNGDRUT"),
+            ("b", "XQEEMOITLZVDSQS"),
+            ("bc", "QNGRKQIHCLGWSCE"),
+            ("bcd", "FMUBYBMAXHNQXCJ"),
+        ];
+        for (key, ciphertxt) in vectors {
+            assert_eq!(Solitaire::new(key).encrypt("AAAAAAAAAAAAAAA"), ciphertxt);
+        }
+        let solitaire = Solitaire::new("cryptonomicon");
+        assert_eq!(solitaire.encrypt("SOLITAIREX"), "KIRAKSFJAN");
+        assert_eq!(solitaire.decrypt("KIRAKSFJAN"), "SOLITAIREX");
+    }
+
+    #[test]
+    fn deck() {
+        let mut solitaire = Solitaire::new("cryptonomicon");
+        solitaire.advance(5);
+        let restored = Solitaire::from_deck(&solitaire.deck()).unwrap();
+        assert_eq!(restored.deck(), solitaire.deck());
+        assert_eq!(restored.keystream(8), solitaire.keystream(8));
+        assert!(Solitaire::from_deck("1 2 3 A B").is_err());
+        assert!(Solitaire::from_deck(&Solitaire::new("").deck().replace("52", "51")).is_err());
+    }
+}
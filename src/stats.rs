@@ -0,0 +1,123 @@
+use crate::common::{char_index, ENGLISH};
+
+pub const CORPUS: &str = include_str!("stats/corpus.txt");
+pub const LETTER_FREQ: [f64; 26] = [
+    0.08167, 0.01492, 0.02782, 0.04253, 0.12702, 0.02228, 0.02015, 0.06094, 0.06966, 0.00153,
+    0.00772, 0.04025, 0.02406, 0.06749, 0.07507, 0.01929, 0.00095, 0.05987, 0.06327, 0.09056,
+    0.02758, 0.00978, 0.02360, 0.00150, 0.01974, 0.00074,
+];
+
+pub fn chi_squared(text: &str) -> f64 {
+    let indices = char_index(text, ENGLISH);
+    let mut counts = [0.0; 26];
+    for &i in indices.iter() {
+        counts[i as usize] += 1.0;
+    }
+    let total = indices.len() as f64;
+    counts
+        .iter()
+        .zip(LETTER_FREQ.iter())
+        .map(|(&count, &freq)| (count - total * freq).powi(2) / (total * freq))
+        .sum()
+}
+
+#[derive(Debug, Clone)]
+pub struct Solution {
+    pub key: String,
+    pub score: f64,
+    pub confidence: f64,
+}
+
+#[derive(Debug)]
+pub struct NGrams {
+    n: usize,
+    logp: Vec<f64>,
+    floor: f64,
+    mean: f64,
+}
+impl NGrams {
+    pub fn new(n: usize, corpus: &str) -> Self {
+        let indices = char_index(&corpus.to_uppercase(), ENGLISH);
+        let mut counts = vec![0.0; 26usize.pow(n as u32)];
+        for window in indices.windows(n) {
+            counts[Self::slot(window)] += 1.0;
+        }
+        let total = indices.len().saturating_sub(n - 1).max(1) as f64;
+        let floor = (0.01 / total).log10();
+        let logp: Vec<f64> = counts
+            .iter()
+            .map(|&c| match c > 0.0 {
+                true => (c / total).log10(),
+                false => floor,
+            })
+            .collect();
+        let mean = counts
+            .iter()
+            .zip(logp.iter())
+            .map(|(c, l)| c * l)
+            .sum::<f64>()
+            / total;
+        Self {
+            n,
+            logp,
+            floor,
+            mean,
+        }
+    }
+    pub fn english(n: usize) -> Self {
+        Self::new(n, CORPUS)
+    }
+
+    fn slot(window: &[u32]) -> usize {
+        window.iter().fold(0, |acc, &i| acc * 26 + i as usize)
+    }
+
+    pub fn score_indices(&self, indices: &[u32]) -> f64 {
+        indices
+            .windows(self.n)
+            .map(|window| self.logp[Self::slot(window)])
+            .sum()
+    }
+    pub fn score(&self, text: &str) -> f64 {
+        self.score_indices(&char_index(text, ENGLISH))
+    }
+    pub fn confidence(&self, indices: &[u32]) -> f64 {
+        let n_windows = indices.len().saturating_sub(self.n - 1);
+        if n_windows == 0 {
+            return 0.0;
+        }
+        let per_window = self.score_indices(indices) / n_windows as f64;
+        ((per_window - self.floor) / (self.mean - self.floor)).clamp(0.0, 1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PLAINTEXT: &str = "THEQUICKBROWNFOXJUMPSOVERTHELAZYDOG";
+
+    #[test]
+    fn chi_english() {
+        let english = include_str!("kryptos/k3_plain.txt");
+        assert!(chi_squared(english) < chi_squared(&english.replace('E', "Z")));
+    }
+
+    #[test]
+    fn ngrams() {
+        let reversed: String = PLAINTEXT.chars().rev().collect();
+        for n in 2..=4 {
+            let ngrams = NGrams::english(n);
+            assert!(ngrams.score(PLAINTEXT) > ngrams.score(&reversed));
+        }
+    }
+
+    #[test]
+    fn confidence() {
+        let quadgrams = NGrams::english(4);
+        let english = char_index(include_str!("kryptos/k1_plain.txt"), ENGLISH);
+        let shifted: Vec<u32> = english.iter().map(|i| (i + 3) % 26).collect();
+        assert!(quadgrams.confidence(&english) > 0.5);
+        assert!(quadgrams.confidence(&shifted) < 0.2);
+    }
+}
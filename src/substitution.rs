@@ -0,0 +1,164 @@
+use crate::common::ENGLISH;
+use crate::common::{
+    alphabetize, char_index, check_unique, filter, keyed_alphabet, refill, Cipher,
+};
+use crate::stats::{NGrams, Solution, LETTER_FREQ};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+#[derive(Debug)]
+pub struct Substitution {
+    alphabet: String,
+    mixed: String,
+}
+impl Substitution {
+    pub fn new(alphabet: &str, key: &str) -> Result<Self, String> {
+        check_unique(alphabet).unwrap();
+        if !key.chars().all(|c| alphabet.contains(c)) {
+            return Err(String::from("Key contains invalid characters!"));
+        }
+        Ok(Self {
+            alphabet: alphabet.to_string(),
+            mixed: keyed_alphabet(key, alphabet),
+        })
+    }
+
+    pub fn key(&self) -> &str {
+        &self.mixed
+    }
+
+    pub fn solve(
+        alphabet: &str,
+        ciphertxt: &str,
+        restarts: usize,
+        iterations: usize,
+        seed: Option<u64>,
+    ) -> Solution {
+        check_unique(alphabet).unwrap();
+        let mut rng = match seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        let n = alphabet.chars().count();
+        let quadgrams = NGrams::english(4);
+        let indices = char_index(&filter(ciphertxt, alphabet), alphabet);
+        // plaintext is scored as English, skipping letters the quadgrams do not cover
+        let english: Vec<Option<u32>> = alphabet
+            .chars()
+            .map(|c| ENGLISH.find(c).map(|i| i as u32))
+            .collect();
+        let decipher = |key: &[u32]| -> Vec<u32> {
+            indices
+                .iter()
+                .filter_map(|&i| english[key[i as usize] as usize])
+                .collect()
+        };
+
+        // start by matching ciphertext letter frequencies to English ones
+        let mut counts = vec![0usize; n];
+        indices.iter().for_each(|&i| counts[i as usize] += 1);
+        let mut by_count: Vec<usize> = (0..n).collect();
+        by_count.sort_by_key(|&i| std::cmp::Reverse(counts[i]));
+        let freq = |p: usize| english[p].map_or(0.0, |e| LETTER_FREQ[e as usize]);
+        let mut by_freq: Vec<usize> = (0..n).collect();
+        by_freq.sort_by(|&a, &b| freq(b).total_cmp(&freq(a)));
+        let mut initial = vec![0u32; n];
+        for (&c, &p) in by_count.iter().zip(by_freq.iter()) {
+            initial[c] = p as u32;
+        }
+
+        let (mut best_key, mut best_score) = (
+            initial.clone(),
+            quadgrams.score_indices(&decipher(&initial)),
+        );
+        for restart in 0..restarts.max(1) {
+            let mut key = best_key.clone();
+            // perturb the best key so far before every restart but the first
+            for _ in 0..restart.min(1) * 8 {
+                key.swap(rng.gen_range(0..n), rng.gen_range(0..n));
+            }
+            let mut score = quadgrams.score_indices(&decipher(&key));
+            for step in 0..iterations {
+                let temperature = 2.0 * (1.0 - step as f64 / iterations as f64);
+                let (a, b) = (rng.gen_range(0..n), rng.gen_range(0..n));
+                key.swap(a, b);
+                let candidate = quadgrams.score_indices(&decipher(&key));
+                let delta = candidate - score;
+                if delta >= 0.0 || rng.gen::<f64>() < (delta / temperature.max(1e-9)).exp() {
+                    score = candidate;
+                    if score > best_score {
+                        (best_key, best_score) = (key.clone(), score);
+                    }
+                } else {
+                    key.swap(a, b);
+                }
+            }
+        }
+
+        let mut mixed = vec![0u32; n];
+        for (c, &p) in best_key.iter().enumerate() {
+            mixed[p as usize] = c as u32;
+        }
+        Solution {
+            key: alphabetize(mixed, alphabet),
+            score: best_score,
+            confidence: quadgrams.confidence(&decipher(&best_key)),
+        }
+    }
+
+    fn substitute(&self, input: &str, decrypt: bool) -> String {
+        let (from, to) = match decrypt {
+            false => (&self.alphabet, &self.mixed),
+            true => (&self.mixed, &self.alphabet),
+        };
+        let indices = char_index(&filter(input, &self.alphabet), from);
+        refill(&alphabetize(indices, to), input, &self.alphabet)
+    }
+}
+impl Cipher for Substitution {
+    fn encrypt(&self, plaintext: &str) -> String {
+        self.substitute(plaintext, false)
+    }
+    fn decrypt(&self, ciphertxt: &str) -> String {
+        self.substitute(ciphertxt, true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::KRYPTOS;
+
+    #[test]
+    fn keyword() {
+        let substitution = Substitution::new(ENGLISH, "KRYPTOS").unwrap();
+        assert_eq!(substitution.key(), KRYPTOS);
+        assert_eq!(substitution.encrypt("BERLIN CLOCK"), "RTLEBG YEHYD");
+        assert_eq!(substitution.decrypt("RTLEBG YEHYD"), "BERLIN CLOCK");
+    }
+
+    #[test]
+    fn explicit() {
+        let key = "QWERTYUIOPASDFGHJKLZXCVBNM";
+        let substitution = Substitution::new(ENGLISH, key).unwrap();
+        assert_eq!(substitution.key(), key);
+        assert_eq!(substitution.encrypt("HELLO, WORLD"), "ITSSG, VGKSR");
+        assert!(Substitution::new(ENGLISH, "lowercase").is_err());
+    }
+
+    #[test]
+    fn solve() {
+        let plaintext = include_str!("kryptos/k3_plain.txt").replace("\n", "");
+        let substitution = Substitution::new(ENGLISH, "HOWARDCARTER").unwrap();
+        let ciphertxt = substitution.encrypt(&plaintext);
+        let solution = Substitution::solve(ENGLISH, &ciphertxt, 4, 10000, Some(1922));
+        let recovered = Substitution::new(ENGLISH, &solution.key).unwrap();
+        let decrypted = recovered.decrypt(&ciphertxt);
+        let correct = decrypted
+            .chars()
+            .zip(plaintext.chars())
+            .filter(|(a, b)| a == b);
+        assert!(correct.count() as f64 > 0.9 * plaintext.len() as f64);
+        assert!(solution.confidence > 0.5);
+    }
+}
@@ -0,0 +1,80 @@
+use crate::common::{check_unique, tokenize, Cipher};
+use crate::transpose::key_digits;
+
+pub const SYLLABARY: [&str; 100] = [
+    "A", "AL", "ALL", "AN", "AND", "AR", "ARE", "AS", "AT", "ATE", "B", "BE", "C", "CE", "CO", "D",
+    "DE", "E", "EA", "ED", "EN", "ENT", "ER", "ERE", "ERS", "ES", "ESS", "F", "FOR", "G", "H",
+    "HA", "HE", "HER", "HI", "I", "IN", "ING", "ION", "IS", "IT", "J", "K", "L", "LE", "M", "ME",
+    "N", "NCE", "ND", "NE", "NG", "NT", "O", "OF", "ON", "OR", "OU", "OUR", "P", "Q", "R", "RA",
+    "RE", "RES", "RO", "S", "SE", "ST", "T", "TE", "TED", "TER", "TH", "THAT", "THE", "TI", "TION",
+    "TO", "U", "UR", "V", "VE", "W", "WAS", "WE", "WH", "X", "Y", "Z", "0", "1", "2", "3", "4",
+    "5", "6", "7", "8", "9",
+];
+
+#[derive(Debug)]
+pub struct Syllabary {
+    rows: Vec<u32>,
+    cols: Vec<u32>,
+}
+impl Syllabary {
+    pub fn new(alphabet: &str, row_key: &str, col_key: &str) -> Result<Self, String> {
+        check_unique(alphabet).unwrap();
+        for key in [row_key, col_key] {
+            if key.chars().count() != 10 || !key.chars().all(|c| alphabet.contains(c)) {
+                return Err(String::from("Keys must have 10 valid characters!"));
+            }
+        }
+        Ok(Self {
+            rows: key_digits(row_key, alphabet),
+            cols: key_digits(col_key, alphabet),
+        })
+    }
+}
+impl Cipher for Syllabary {
+    fn encrypt(&self, plaintext: &str) -> String {
+        tokenize(plaintext, &SYLLABARY)
+            .into_iter()
+            .map(|t| format!("{}{}", self.rows[t / 10], self.cols[t % 10]))
+            .collect()
+    }
+    fn decrypt(&self, ciphertxt: &str) -> String {
+        let digits: Vec<u32> = ciphertxt.chars().filter_map(|c| c.to_digit(10)).collect();
+        digits
+            .chunks_exact(2)
+            .filter_map(|pair| {
+                let row = self.rows.iter().position(|&d| d == pair[0])?;
+                let col = self.cols.iter().position(|&d| d == pair[1])?;
+                Some(SYLLABARY[row * 10 + col])
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::ENGLISH;
+
+    #[test]
+    fn syllabary() {
+        let plaintext = "THE ENEMY WAS SEEN AT 0400";
+        let syllabary = Syllabary::new(ENGLISH, "BLACKSTONE", "LUMBERJACK").unwrap();
+        // THE EN E M Y WAS SE EN AT 0 4 0 0
+        let ciphertxt = "89176159737401172347444747";
+        assert_eq!(syllabary.encrypt(plaintext), ciphertxt);
+        assert_eq!(syllabary.decrypt(ciphertxt), plaintext.replace(" ", ""));
+        assert_eq!(syllabary.rows, [2, 6, 1, 3, 5, 9, 0, 8, 7, 4]);
+        assert_eq!(syllabary.cols, [7, 0, 8, 2, 4, 9, 5, 1, 3, 6]);
+        for (unit, code) in [("THE", "89"), ("WAS", "74"), ("TION", "81"), ("0", "47")] {
+            assert_eq!(syllabary.encrypt(unit), code);
+        }
+    }
+
+    #[test]
+    fn coordinates() {
+        let syllabary = Syllabary::new(ENGLISH, "ABCDEFGHIJ", "ABCDEFGHIJ").unwrap();
+        assert_eq!(syllabary.encrypt("AND THE"), "1586");
+        assert_eq!(syllabary.decrypt("1586"), "ANDTHE");
+        assert!(Syllabary::new(ENGLISH, "SHORT", "ABCDEFGHIJ").is_err());
+    }
+}
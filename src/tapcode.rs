@@ -0,0 +1,184 @@
+use crate::common::Cipher;
+use crate::polybius::Square;
+use std::str::FromStr;
+
+// K is sent as C
+pub const TAP: &str = "ABCDEFGHIJLMNOPQRSTUVWXYZ";
+pub const KNOCK: char = '.';
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Style {
+    Dots,
+    Digits,
+}
+impl FromStr for Style {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "dots" => Ok(Style::Dots),
+            "digits" => Ok(Style::Digits),
+            _ => Err(format!("Unknown style: {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Separators {
+    pub pair: String,
+    pub letter: String,
+    pub word: String,
+}
+impl Separators {
+    pub fn new(style: Style) -> Self {
+        let (pair, letter) = match style {
+            Style::Dots => (" ", "  "),
+            Style::Digits => ("", " "),
+        };
+        Self {
+            pair: pair.to_string(),
+            letter: letter.to_string(),
+            word: String::from(" / "),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct TapCode {
+    square: Square,
+    style: Style,
+    separators: Separators,
+}
+impl TapCode {
+    pub fn new(alphabet: &str, style: Style, separators: Separators) -> Result<Self, String> {
+        let square = Square::new(alphabet, "")?;
+        if square.size() > 9 {
+            return Err(String::from("Square must not exceed 9 rows!"));
+        }
+        if separators.word.is_empty() {
+            return Err(String::from("Word separator must not be empty!"));
+        }
+        let taps = |s: &str| s.contains(KNOCK) || s.chars().any(|c| c.is_ascii_digit());
+        if taps(&separators.pair) || taps(&separators.letter) || taps(&separators.word) {
+            return Err(String::from("Separators must not contain taps!"));
+        }
+        if style == Style::Dots && separators.pair.is_empty() {
+            return Err(String::from("Knock runs need a pair separator!"));
+        }
+        Ok(Self {
+            square,
+            style,
+            separators,
+        })
+    }
+
+    fn taps(&self, n: usize) -> String {
+        match self.style {
+            Style::Dots => KNOCK.to_string().repeat(n),
+            Style::Digits => n.to_string(),
+        }
+    }
+
+    fn counts(&self, word: &str) -> Vec<usize> {
+        match self.style {
+            Style::Dots => word
+                .split(|c| c != KNOCK)
+                .filter(|run| !run.is_empty())
+                .map(|run| run.len())
+                .collect(),
+            Style::Digits => word
+                .chars()
+                .filter_map(|c| c.to_digit(10).map(|d| d as usize))
+                .collect(),
+        }
+    }
+}
+impl Cipher for TapCode {
+    fn encrypt(&self, plaintext: &str) -> String {
+        let alphabet = self.square.alphabet();
+        plaintext
+            .to_uppercase()
+            .split_whitespace()
+            .map(|word| {
+                word.chars()
+                    .map(|c| match c {
+                        'K' if !alphabet.contains('K') => 'C',
+                        'J' if !alphabet.contains('J') => 'I',
+                        _ => c,
+                    })
+                    .filter_map(|c| self.square.locate(c))
+                    .map(|(row, col)| {
+                        [self.taps(row + 1), self.taps(col + 1)].join(&self.separators.pair)
+                    })
+                    .collect::<Vec<String>>()
+                    .join(&self.separators.letter)
+            })
+            .filter(|word| !word.is_empty())
+            .collect::<Vec<String>>()
+            .join(&self.separators.word)
+    }
+    fn decrypt(&self, ciphertxt: &str) -> String {
+        let word = match self.separators.word.trim() {
+            "" => self.separators.word.as_str(),
+            trimmed => trimmed,
+        };
+        let size = self.square.size();
+        ciphertxt
+            .split(word)
+            .map(|word| {
+                self.counts(word)
+                    .chunks_exact(2)
+                    .filter(|pair| pair.iter().all(|&n| (1..=size).contains(&n)))
+                    .map(|pair| self.square.at(pair[0] - 1, pair[1] - 1))
+                    .collect::<String>()
+            })
+            .filter(|word| !word.is_empty())
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn digits() {
+        let tap = TapCode::new(TAP, Style::Digits, Separators::new(Style::Digits)).unwrap();
+        assert_eq!(tap.encrypt("Hello kid"), "23 15 31 31 34 / 13 24 14");
+        assert_eq!(tap.decrypt("23 15 31 31 34 / 13 24 14"), "HELLO CID");
+        assert_eq!(tap.decrypt("23  15\n3131 34/1324 14"), "HELLO CID");
+    }
+
+    #[test]
+    fn dots() {
+        let tap = TapCode::new(TAP, Style::Dots, Separators::new(Style::Dots)).unwrap();
+        let ciphertxt = tap.encrypt("WATER");
+        assert_eq!(ciphertxt, "..... ..  . .  .... ....  . .....  .... ..");
+        assert_eq!(tap.decrypt(&ciphertxt), "WATER");
+        assert_eq!(
+            tap.decrypt(".....|..\t.|.   ....  ....\n.|.....  ....|.."),
+            "WATER"
+        );
+
+        let separators = Separators {
+            pair: String::from("-"),
+            letter: String::from(" "),
+            word: String::from("   "),
+        };
+        let tap = TapCode::new(TAP, Style::Dots, separators).unwrap();
+        assert_eq!(
+            tap.encrypt("NO WAY"),
+            "...-... ...-....   .....-.. .-. .....-...."
+        );
+        assert_eq!(tap.decrypt(&tap.encrypt("NO WAY")), "NO WAY");
+    }
+
+    #[test]
+    fn invalid() {
+        let mut separators = Separators::new(Style::Digits);
+        assert!(TapCode::new(TAP, Style::Dots, separators.clone()).is_err());
+        separators.letter = String::from("0");
+        assert!(TapCode::new(TAP, Style::Digits, separators).is_err());
+        assert_eq!("Dots".parse::<Style>(), Ok(Style::Dots));
+    }
+}
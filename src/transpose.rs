@@ -1,5 +1,5 @@
 use crate::common::Cipher;
-use crate::common::{alphabetize, char_index, check_unique, filter, refill};
+use crate::common::{alphabetize, char_index, check_unique, filter, keyed_alphabet, refill};
 use rand::Rng;
 
 use ndarray::{s, Array1};
@@ -67,7 +67,7 @@ impl Transpose {
     fn transpose(&self, input: &str, decrypt: bool) -> String {
         let clean = filter(input, &self.alphabet);
         let mut n_rows = self.num_rows;
-        let mut n_cols = (clean.len() + self.num_rows - 1) / self.num_rows;
+        let mut n_cols = clean.len().div_ceil(self.num_rows);
         if decrypt {
             (n_cols, n_rows) = (self.num_rows, n_cols)
         }
@@ -122,7 +122,7 @@ impl Cipher for Columnar {
     fn encrypt(&self, plaintext: &str) -> String {
         let clean = filter(plaintext, &self.alphabet);
         let n_cols = self.keyword.len();
-        let n_rows = (clean.len() + n_cols - 1) / n_cols;
+        let n_rows = clean.len().div_ceil(n_cols);
         let n_pad = n_cols * n_rows - clean.len();
 
         let mut rng = rand::thread_rng();
@@ -144,8 +144,7 @@ impl Cipher for Columnar {
 
         let joined = cols
             .into_iter()
-            .map(|col| col.into_iter().skip(1))
-            .flatten()
+            .flat_map(|col| col.into_iter().skip(1))
             .collect();
         let output = alphabetize(joined, &self.alphabet);
         refill(&output, plaintext, &self.alphabet)
@@ -154,7 +153,7 @@ impl Cipher for Columnar {
     fn decrypt(&self, ciphertxt: &str) -> String {
         let clean = filter(ciphertxt, &self.alphabet);
         let n_cols = self.keyword.len();
-        let n_rows = (clean.len() + n_cols - 1) / n_cols;
+        let n_rows = clean.len().div_ceil(n_cols);
 
         let kw_idx = char_index(&self.keyword, &self.alphabet);
         let mut kw_idx_to_sort = kw_idx.clone();
@@ -175,8 +174,7 @@ impl Cipher for Columnar {
 
         let joined = cols
             .into_iter()
-            .map(|col| col.into_iter().skip(1))
-            .flatten()
+            .flat_map(|col| col.into_iter().skip(1))
             .collect();
         let transpose = transpose_vec(joined, (n_cols, n_rows));
         let output = alphabetize(transpose, &self.alphabet);
@@ -184,6 +182,240 @@ impl Cipher for Columnar {
     }
 }
 
+pub fn key_order(keyword: &str, alphabet: &str) -> Vec<usize> {
+    let kw_idx = char_index(keyword, alphabet);
+    let mut order: Vec<usize> = (0..kw_idx.len()).collect();
+    order.sort_by_key(|&i| kw_idx[i]);
+    order
+}
+
+pub fn key_digits(keyword: &str, alphabet: &str) -> Vec<u32> {
+    let mut digits = vec![0; keyword.chars().count()];
+    for (rank, col) in key_order(keyword, alphabet).into_iter().enumerate() {
+        digits[col] = (rank as u32 + 1) % 10;
+    }
+    digits
+}
+
+pub fn columnar_alphabet(keyword: &str, alphabet: &str) -> String {
+    let keyed: Vec<char> = keyed_alphabet(keyword, alphabet).chars().collect();
+    let n_cols = keyed
+        .iter()
+        .take_while(|c| keyword.contains(**c))
+        .count()
+        .max(1);
+    let header: String = keyed[..n_cols].iter().collect();
+    key_order(&header, alphabet)
+        .into_iter()
+        .flat_map(|col| keyed.iter().skip(col).step_by(n_cols))
+        .collect()
+}
+
+// output k is input positions[k]
+fn permute(input: &str, alphabet: &str, positions: &[usize], decrypt: bool) -> String {
+    let chars: Vec<char> = filter(input, alphabet).chars().collect();
+    let mut output = chars.clone();
+    for (k, &pos) in positions.iter().enumerate() {
+        match decrypt {
+            false => output[k] = chars[pos],
+            true => output[pos] = chars[k],
+        }
+    }
+    refill(&output.into_iter().collect::<String>(), input, alphabet)
+}
+
+fn amsco_order(len: usize, order: &[usize], digraph_first: bool) -> Vec<usize> {
+    let n_cols = order.len();
+    let mut cells: Vec<Vec<usize>> = vec![Vec::new(); n_cols];
+    let (mut start, mut row) = (0, 0);
+    while start < len {
+        for (col, cell) in cells.iter_mut().enumerate() {
+            let size = match ((row + col) % 2 == 0) == digraph_first {
+                true => 2,
+                false => 1,
+            };
+            cell.extend(start..len.min(start + size));
+            start += size;
+        }
+        row += 1;
+    }
+    order.iter().flat_map(|&col| cells[col].clone()).collect()
+}
+
+#[derive(Debug)]
+pub struct Amsco {
+    alphabet: String,
+    order: Vec<usize>,
+    digraph_first: bool,
+}
+impl Amsco {
+    pub fn new(alphabet: &str, keyword: &str) -> Result<Self, String> {
+        check_unique(alphabet).unwrap();
+        if keyword.is_empty() || filter(keyword, alphabet) != keyword {
+            return Err(String::from("Keyword contains invalid characters!"));
+        }
+        Ok(Self {
+            alphabet: alphabet.to_string(),
+            order: key_order(keyword, alphabet),
+            digraph_first: true,
+        })
+    }
+    pub fn new_single(alphabet: &str, keyword: &str) -> Result<Self, String> {
+        Ok(Self {
+            digraph_first: false,
+            ..Self::new(alphabet, keyword)?
+        })
+    }
+
+    fn transpose(&self, input: &str, decrypt: bool) -> String {
+        let len = filter(input, &self.alphabet).chars().count();
+        let positions = amsco_order(len, &self.order, self.digraph_first);
+        permute(input, &self.alphabet, &positions, decrypt)
+    }
+}
+impl Cipher for Amsco {
+    fn encrypt(&self, plaintext: &str) -> String {
+        self.transpose(plaintext, false)
+    }
+    fn decrypt(&self, ciphertxt: &str) -> String {
+        self.transpose(ciphertxt, true)
+    }
+}
+
+// W shares the row of V
+pub const CADENUS: &str = "ABCDEFGHIJKLMNOPQRSTUVXYZ";
+
+fn cadenus_order(len: usize, order: &[usize], shifts: &[usize]) -> Vec<usize> {
+    let n_cols = order.len();
+    let n_rows = CADENUS.len();
+    let mut positions = Vec::with_capacity(len);
+    for start in (0..len).step_by(n_rows * n_cols) {
+        let end = len.min(start + n_rows * n_cols);
+        let columns: Vec<Vec<usize>> = order
+            .iter()
+            .map(|&col| (start + col..end).step_by(n_cols).collect())
+            .collect();
+        for row in 0..n_rows {
+            for (column, &shift) in columns.iter().zip(shifts) {
+                if row < column.len() {
+                    let at = (row + n_rows * n_cols - shift) % column.len();
+                    positions.push(column[at]);
+                }
+            }
+        }
+    }
+    positions
+}
+
+#[derive(Debug)]
+pub struct Cadenus {
+    alphabet: String,
+    order: Vec<usize>,
+    shifts: Vec<usize>,
+}
+impl Cadenus {
+    pub fn new(alphabet: &str, keyword: &str) -> Result<Self, String> {
+        check_unique(alphabet).unwrap();
+        let keyword = keyword.to_uppercase();
+        let shifts = keyword
+            .chars()
+            .map(|c| CADENUS.find(if c == 'W' { 'V' } else { c }))
+            .collect::<Option<Vec<usize>>>();
+        let order = key_order(&keyword, alphabet);
+        match shifts {
+            Some(shifts) if !shifts.is_empty() && order.len() == shifts.len() => Ok(Self {
+                alphabet: alphabet.to_string(),
+                shifts: order.iter().map(|&col| shifts[col]).collect(),
+                order,
+            }),
+            _ => Err(String::from("Keyword contains invalid characters!")),
+        }
+    }
+
+    fn transpose(&self, input: &str, decrypt: bool) -> String {
+        let len = filter(input, &self.alphabet).chars().count();
+        let positions = cadenus_order(len, &self.order, &self.shifts);
+        permute(input, &self.alphabet, &positions, decrypt)
+    }
+}
+impl Cipher for Cadenus {
+    fn encrypt(&self, plaintext: &str) -> String {
+        self.transpose(plaintext, false)
+    }
+    fn decrypt(&self, ciphertxt: &str) -> String {
+        self.transpose(ciphertxt, true)
+    }
+}
+
+fn swagman_order(len: usize, square: &[Vec<usize>]) -> Vec<usize> {
+    let n = square.len();
+    let width = len / n;
+    let mut positions = vec![0; len];
+    for row in 0..n {
+        for col in 0..width {
+            positions[col * n + square[row][col % n]] = row * width + col;
+        }
+    }
+    positions
+}
+
+#[derive(Debug)]
+pub struct Swagman {
+    alphabet: String,
+    square: Vec<Vec<usize>>,
+}
+impl Swagman {
+    pub fn new(alphabet: &str, square: &str) -> Result<Self, String> {
+        check_unique(alphabet).unwrap();
+        let digits = square
+            .chars()
+            .filter(|c| !c.is_whitespace() && *c != ',')
+            .map(|c| c.to_digit(10).map(|d| d as usize))
+            .collect::<Option<Vec<usize>>>()
+            .ok_or(String::from("Key square contains invalid characters!"))?;
+        let n = (2..10).find(|n| n * n == digits.len()).unwrap_or(0);
+        let square: Vec<Vec<usize>> = digits.chunks(n.max(1)).map(|row| row.to_vec()).collect();
+        let latin = (0..n).all(|i| {
+            (1..=n).all(|d| square[i].contains(&d) && square.iter().any(|row| row[i] == d))
+        });
+        if n == 0 || !latin {
+            return Err(String::from("Key must be a Latin square of digits!"));
+        }
+        Ok(Self {
+            alphabet: alphabet.to_string(),
+            square: square
+                .into_iter()
+                .map(|row| row.into_iter().map(|d| d - 1).collect())
+                .collect(),
+        })
+    }
+}
+impl Cipher for Swagman {
+    fn encrypt(&self, plaintext: &str) -> String {
+        let clean = filter(plaintext, &self.alphabet);
+        let n = self.square.len();
+        let n_pad = (n - clean.len() % n) % n;
+
+        let mut rng = rand::thread_rng();
+        let rand_pad: String = (0..n_pad)
+            .map(|_| {
+                let i = rng.gen_range(0..self.alphabet.chars().count());
+                self.alphabet.chars().nth(i).unwrap()
+            })
+            .collect();
+        let padded = format!("{}{}", plaintext, rand_pad);
+        let positions = swagman_order(clean.len() + n_pad, &self.square);
+        permute(&padded, &self.alphabet, &positions, false)
+    }
+    fn decrypt(&self, ciphertxt: &str) -> String {
+        let len = filter(ciphertxt, &self.alphabet).chars().count();
+        let whole = len - len % self.square.len();
+        let mut positions = swagman_order(whole, &self.square);
+        positions.extend(whole..len);
+        permute(ciphertxt, &self.alphabet, &positions, true)
+    }
+}
+
 pub struct RailFence {}
 impl RailFence {}
 
@@ -199,11 +431,11 @@ mod tests {
         let plaintext = PLAINTEXT.replace("AT ONCE", "QUICKLY");
         let ciphertxt = "WO EEV QAEURRIEEC DDKI FLSLYCE";
 
-        let pad_rows = Transpose::as_flip(&ENGLISH, 3, false).unwrap();
+        let pad_rows = Transpose::as_flip(ENGLISH, 3, false).unwrap();
         assert_eq!(pad_rows.encrypt(&plaintext), ciphertxt);
         assert_eq!(pad_rows.decrypt(ciphertxt), plaintext);
 
-        let pad_cols = Transpose::as_flip(&ENGLISH, 3, true).unwrap();
+        let pad_cols = Transpose::as_flip(ENGLISH, 3, true).unwrap();
         assert_eq!(pad_cols.encrypt(&plaintext), ciphertxt);
         assert_eq!(pad_cols.decrypt(ciphertxt), plaintext);
     }
@@ -211,20 +443,20 @@ mod tests {
     #[test]
     fn scytale_asym() {
         let ciphertxt = "WO EEV AAETRROEEN DDCI FE SLCE";
-        let pad_rows = Transpose::as_flip(&ENGLISH, 3, false).unwrap();
+        let pad_rows = Transpose::as_flip(ENGLISH, 3, false).unwrap();
         assert_eq!(pad_rows.encrypt(PLAINTEXT), ciphertxt);
-        assert_eq!(pad_rows.decrypt(&ciphertxt), PLAINTEXT);
+        assert_eq!(pad_rows.decrypt(ciphertxt), PLAINTEXT);
 
         let ciphertxt = "WO EEV EAEARRTEEO DDNI FC SLEC";
-        let pad_cols = Transpose::as_flip(&ENGLISH, 3, true).unwrap();
+        let pad_cols = Transpose::as_flip(ENGLISH, 3, true).unwrap();
         assert_eq!(pad_cols.encrypt(PLAINTEXT), ciphertxt);
-        assert_eq!(pad_cols.decrypt(&ciphertxt), PLAINTEXT);
+        assert_eq!(pad_cols.decrypt(ciphertxt), PLAINTEXT);
     }
 
     #[test]
     fn columnar() {
         let ciphertxt = "EV LN* ACDT*ESEA* ROFO *D EEC*WIREE";
-        let pad_rows = Columnar::new(&ENGLISH, "ZEBRAS").unwrap();
+        let pad_rows = Columnar::new(ENGLISH, "ZEBRAS").unwrap();
         let encrypted = pad_rows.encrypt(PLAINTEXT);
         let cleaned: String = ciphertxt
             .chars()
@@ -235,14 +467,69 @@ mod tests {
         assert_eq!(pad_rows.decrypt(&encrypted)[..PLAINTEXT.len()], *PLAINTEXT);
     }
 
+    #[test]
+    fn amsco() {
+        let plaintext = "INCOMPLETE COLUMNAR WITH ALTERNATING SINGLE LETTERS AND DIGRAPHS";
+        let ciphertxt = "CECRTEGLEN PHPLUTNANT EIOMOWIRSI TDDSINTNAL INESAALEMH ATGLRGR";
+        let amsco = Amsco::new(ENGLISH, "DACBE").unwrap();
+        assert_eq!(
+            amsco.encrypt(&plaintext.replace(" ", "")),
+            ciphertxt.replace(" ", "")
+        );
+        assert_eq!(
+            amsco.decrypt(ciphertxt),
+            "INCOMPLETE COLUMNARWI THALTERNAT INGSINGLEL ETTERSANDD IGRAPHS"
+        );
+
+        let single = Amsco::new_single(ENGLISH, "ZEBRA").unwrap();
+        assert_eq!(single.decrypt(&single.encrypt(PLAINTEXT)), PLAINTEXT);
+        assert!(Amsco::new(ENGLISH, "").is_err());
+    }
+
+    #[test]
+    fn cadenus() {
+        let plaintext = "ASEVERELIMITATIONONTHEUSEFULNESSOFTHECADENUSISTHATEVERYMESSAGEMUSTBEAMULTIPLEOFTWENTYFIVELETTERSLONG";
+        let ciphertxt = "SYSTRETOMTATTLUSOATLEEESFIYHEASDFNMSCHBHNEUVSNPMTOFARENUSEIEEIELTARLMENTIEETOGEVESITFAISLTNGEEUVOWUL";
+        let cadenus = Cadenus::new(ENGLISH, "EASY").unwrap();
+        assert_eq!(cadenus.encrypt(plaintext), ciphertxt);
+        assert_eq!(cadenus.decrypt(ciphertxt), plaintext);
+
+        let long = format!("{}{}", plaintext, PLAINTEXT);
+        let cadenus = Cadenus::new(ENGLISH, "WIND").unwrap();
+        assert_eq!(cadenus.decrypt(&cadenus.encrypt(&long)), long);
+        assert!(Cadenus::new(ENGLISH, "EAS1").is_err());
+    }
+
+    #[test]
+    fn swagman() {
+        let swagman = Swagman::new(ENGLISH, "1234 3412 2143 4321").unwrap();
+        // regression vector, not the published ACA example; a whole number of rows, so no
+        // random padding is added
+        let plaintext = "WEAREDISCOVEREDFLEEATONCEQKJ";
+        let ciphertxt = "WDSOFENCOCALEVEREEEQADKREJIT";
+        assert_eq!(swagman.encrypt(plaintext), ciphertxt);
+        assert_eq!(swagman.decrypt(ciphertxt), plaintext);
+        let ciphertxt = swagman.encrypt("WEAREDISCOVEREDFLEEATONCE");
+        assert_eq!(ciphertxt.len(), 28);
+        assert!(swagman
+            .decrypt(&ciphertxt)
+            .starts_with("WEAREDISCOVEREDFLEEATONCE"));
+
+        let swagman = Swagman::new(ENGLISH, "123 231 312").unwrap();
+        assert_eq!(swagman.encrypt("ABCDEF"), "ACEFBD");
+        assert_eq!(swagman.decrypt("ACEFBD"), "ABCDEF");
+        assert!(Swagman::new(ENGLISH, "123 213 312").is_err());
+        assert!(Swagman::new(ENGLISH, "12 21 1").is_err());
+    }
+
     #[test]
     fn kryptos_k3() {
         let plaintext = include_str!("kryptos/k3_plain.txt").replace("\n", "");
         let ciphertxt = include_str!("kryptos/k3_cipher.txt").replace("\n", "");
 
         // ENCRYPT
-        let left8 = Transpose::as_left(&KRYPTOS, 8, false).unwrap();
-        let left24 = Transpose::as_left(&KRYPTOS, 24, false).unwrap();
+        let left8 = Transpose::as_left(KRYPTOS, 8, false).unwrap();
+        let left24 = Transpose::as_left(KRYPTOS, 24, false).unwrap();
         let rotated_8x42 = left8.encrypt(&plaintext);
         let rotated_24x14 = left24.encrypt(&rotated_8x42);
         assert_eq!(rotated_24x14.replace(" ", ""), ciphertxt.replace(" ", ""));
@@ -253,8 +540,8 @@ mod tests {
         assert_eq!(rotated_42x8.replace(" ", ""), plaintext.replace(" ", ""));
 
         // INVERSE ENCRYPT = DECRYPT
-        let right14 = Transpose::as_right(&KRYPTOS, 14, false).unwrap();
-        let right42 = Transpose::as_right(&KRYPTOS, 42, false).unwrap();
+        let right14 = Transpose::as_right(KRYPTOS, 14, false).unwrap();
+        let right42 = Transpose::as_right(KRYPTOS, 42, false).unwrap();
         let rotated_14x24 = right14.encrypt(&ciphertxt);
         let rotated_42x8 = right42.encrypt(&rotated_14x24);
         assert_eq!(rotated_42x8.replace(" ", ""), plaintext.replace(" ", ""));
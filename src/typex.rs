@@ -0,0 +1,195 @@
+use crate::common::{Cipher, ENGLISH};
+use crate::rotor::{DoubleStep, Machine, Plugboard, Reflector, Rotor, Stator};
+
+// the service wirings were never published
+pub const ROTORS: [&str; 8] = [
+    "MCYLPQUVRXGSAOWNBJEZDTFKHI",
+    "KHWENRCBISXJQGOFMAPVYZDLTU",
+    "BYPDZMGIKQCUSATREHOJNLFWXV",
+    "ZANJCGDLVHIXOBRPMSWQUKFYET",
+    "QXBGUTOVFCZPJIHSWERYNDAMLK",
+    "BDCNWUEIQVFTSXALOGZJYMHKPR",
+    "WJUKEIABMSGFTQZVCNPHORDXYL",
+    "TNVCZXDIPFWQKHSJMAOYLEURGB",
+];
+pub const NOTCHES: &str = "BFHNQUW";
+pub const REFLECTOR: &str = "AN BC FG IE KD LU MH OR TS VZ WQ XJ YP";
+pub const N_ROTORS: usize = 5;
+pub const N_STATORS: usize = 2;
+
+// `R` marks a reversed rotor
+pub fn parse_order(order: &str) -> Result<Vec<(usize, bool)>, String> {
+    let order = order
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            let (n, reversed) = match s.strip_suffix(['R', 'r']) {
+                Some(n) => (n, true),
+                None => (s, false),
+            };
+            n.parse().ok().map(|n| (n, reversed))
+        })
+        .collect::<Option<Vec<(usize, bool)>>>()
+        .ok_or(String::from("Rotor order contains invalid characters!"))?;
+    if order.len() != N_ROTORS
+        || order.iter().any(|&(n, _)| n == 0 || n > ROTORS.len())
+        || (1..N_ROTORS).any(|i| order[..i].iter().any(|&(n, _)| n == order[i].0))
+    {
+        return Err(format!(
+            "Order must pick {} distinct rotors from 1 to {}!",
+            N_ROTORS,
+            ROTORS.len()
+        ));
+    }
+    Ok(order)
+}
+
+#[derive(Debug)]
+pub struct Typex {
+    order: String,
+    rings: String,
+    reflector: String,
+    stators: String,
+    machine: Machine,
+}
+impl Typex {
+    pub fn new(order: &str, rings: &str, positions: &str, reflector: &str) -> Result<Self, String> {
+        let wheels = parse_order(order)?;
+        let letters = |s: &str| -> Result<Vec<usize>, String> {
+            let indices: Option<Vec<usize>> = s.chars().map(|c| ENGLISH.find(c)).collect();
+            match indices {
+                Some(indices) if indices.len() == N_ROTORS => Ok(indices),
+                _ => Err(format!("Expected {} letters: {}", N_ROTORS, s)),
+            }
+        };
+        let (ring_letters, position_letters) = (letters(rings)?, letters(positions)?);
+
+        let mut rotors = Vec::with_capacity(N_ROTORS);
+        for (i, &(n, reversed)) in wheels.iter().enumerate() {
+            let rotor = Rotor::new(ENGLISH, ROTORS[n - 1], NOTCHES)?;
+            let mut rotor = if reversed { rotor.reversed() } else { rotor };
+            rotor.set_ring(ring_letters[i]);
+            rotor.set_position(position_letters[i]);
+            rotors.push(rotor);
+        }
+        let stators = rotors.split_off(N_ROTORS - N_STATORS);
+        let machine = Machine::new(
+            ENGLISH,
+            Plugboard::new(ENGLISH, "")?,
+            Stator::identity(ENGLISH.len()),
+            rotors,
+            stators.iter().map(Stator::from_rotor).collect(),
+            Some(Reflector::from_pairs(ENGLISH, reflector)?),
+            Box::new(DoubleStep),
+        )?;
+        Ok(Self {
+            order: order.to_string(),
+            rings: rings.to_string(),
+            reflector: reflector.to_string(),
+            stators: positions[N_ROTORS - N_STATORS..].to_string(),
+            machine,
+        })
+    }
+
+    // all five wheels, stators included
+    pub fn positions(&self) -> String {
+        format!("{}{}", self.machine.positions(), self.stators)
+    }
+    // the stators are fixed in the machine, so it is rebuilt around them
+    pub fn set_positions(&mut self, positions: &str) -> Result<(), String> {
+        *self = Self::new(&self.order, &self.rings, positions, &self.reflector)?;
+        Ok(())
+    }
+}
+impl Cipher for Typex {
+    fn encrypt(&self, plaintext: &str) -> String {
+        self.machine.encrypt(plaintext)
+    }
+    fn decrypt(&self, ciphertxt: &str) -> String {
+        self.machine.decrypt(ciphertxt)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rotor::Stepping;
+
+    const PLAINTEXT: &str = "TYPEX IMPROVED ON ENIGMA";
+
+    #[test]
+    fn reciprocal() {
+        let mut typex = Typex::new("1 2R 3 4 5", "AAAAA", "ABCDE", REFLECTOR).unwrap();
+        let ciphertxt = typex.encrypt(PLAINTEXT);
+        assert_eq!(typex.decrypt(&ciphertxt), PLAINTEXT);
+        assert!(ciphertxt
+            .chars()
+            .zip(PLAINTEXT.chars())
+            .all(|(c, p)| c == ' ' || c != p));
+
+        typex.set_positions("XYZAB").unwrap();
+        assert_eq!(typex.positions(), "XYZAB");
+        assert_ne!(typex.encrypt(PLAINTEXT), ciphertxt);
+        typex.set_positions("ABCDE").unwrap();
+        assert_eq!(typex.encrypt(PLAINTEXT), ciphertxt);
+        assert!(typex.set_positions("XYZ").is_err());
+    }
+
+    #[test]
+    fn settings() {
+        let base = Typex::new("1 2 3 4 5", "AAAAA", "AAAAA", REFLECTOR).unwrap();
+        let ciphertxt = base.encrypt(PLAINTEXT);
+        for (order, positions) in [("1R 2 3 4 5", "AAAAA"), ("1 2 3 4R 5", "AAAAA")] {
+            let typex = Typex::new(order, "AAAAA", positions, REFLECTOR).unwrap();
+            assert_ne!(typex.encrypt(PLAINTEXT), ciphertxt);
+        }
+        // the stators take no part in stepping, but their settings still change the key
+        let typex = Typex::new("1 2 3 4 5", "AAAAA", "AAAAB", REFLECTOR).unwrap();
+        assert_ne!(typex.encrypt(PLAINTEXT), ciphertxt);
+        assert_eq!(typex.positions(), "AAAAB");
+
+        assert!(parse_order("1 2 3 4").is_err());
+        assert!(parse_order("1 2 3 4 4R").is_err());
+        assert!(Typex::new("1 2 3 4 5", "AAAA", "AAAAA", REFLECTOR).is_err());
+        assert!(Typex::new("1 2 3 4 5", "AAAAA", "AAAAA", "AB BC").is_err());
+    }
+
+    #[test]
+    fn notches() {
+        // over one revolution of the fast rotor, Enigma's single notch moves the middle rotor
+        // once; seven notches move it ten times, three of them double steps
+        for (notches, middle, left) in [("Q", 1, 0), (NOTCHES, 10, 3)] {
+            let mut rotors: Vec<Rotor> = (0..3)
+                .map(|n| Rotor::new(ENGLISH, ROTORS[n], notches).unwrap())
+                .collect();
+            for _ in 0..26 {
+                DoubleStep.step(&mut rotors);
+            }
+            assert_eq!((rotors[1].position(), rotors[0].position()), (middle, left));
+        }
+
+        // a reversed rotor keeps its notches where they are on the tyre
+        let mut rotor = Rotor::new(ENGLISH, ROTORS[1], NOTCHES).unwrap().reversed();
+        let at_notch: String = (0..26)
+            .filter_map(|i| {
+                rotor.set_position(i);
+                rotor.at_notch().then(|| ENGLISH.chars().nth(i).unwrap())
+            })
+            .collect();
+        assert_eq!(at_notch, NOTCHES);
+    }
+
+    #[test]
+    fn regression() {
+        // regression vectors from this implementation, not a published source; from AEGDE
+        // the reversed middle rotor moves on to its notch at F
+        for (positions, ciphertxt) in [
+            ("ABCDE", "JKDGV UCZNUFFL UU NRADQO"),
+            ("AEGDE", "FUOWH PCBSHEXL GY DUQDDS"),
+        ] {
+            let typex = Typex::new("1 2R 3 4 5", "AAAAA", positions, REFLECTOR).unwrap();
+            assert_eq!(typex.encrypt(PLAINTEXT), ciphertxt);
+            assert_eq!(typex.decrypt(ciphertxt), PLAINTEXT);
+        }
+    }
+}
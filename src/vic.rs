@@ -0,0 +1,350 @@
+use crate::common::{check_unique, filter, Cipher, ENGLISH};
+use crate::gromark::chain_digits;
+use crate::transpose::{key_digits, key_order};
+use rand::Rng;
+
+pub const DIGITS: &str = "1234567890";
+pub const TOP_ROW: &str = "AT ONE SIR";
+
+fn digits(input: &str) -> Vec<u32> {
+    input.chars().filter_map(|c| c.to_digit(10)).collect()
+}
+fn digit_str(digits: &[u32]) -> String {
+    digits
+        .iter()
+        .map(|&d| char::from_digit(d, 10).unwrap())
+        .collect()
+}
+
+#[derive(Debug)]
+pub struct Checkerboard {
+    alphabet: String,
+    header: Vec<u32>,
+    rows: Vec<Vec<Option<char>>>,
+}
+impl Checkerboard {
+    pub fn new(alphabet: &str, top: &str, header: &str) -> Result<Self, String> {
+        check_unique(alphabet).unwrap();
+        let top: Vec<char> = top.chars().collect();
+        if top.len() != 10 || top.iter().filter(|&&c| c == ' ').count() != 2 {
+            return Err(String::from(
+                "Top row must have 8 letters and 2 blanks in 10 positions!",
+            ));
+        }
+        let header = digits(header);
+        if header.len() != 10 || (0..10).any(|d| !header.contains(&d)) {
+            return Err(String::from("Header must be a permutation of 10 digits!"));
+        }
+        let letters: Vec<char> = top.iter().filter(|&&c| c != ' ').copied().collect();
+        if !letters.iter().all(|&c| alphabet.contains(c)) {
+            return Err(String::from("Top row contains invalid characters!"));
+        }
+        let rest: Vec<char> = alphabet.chars().filter(|c| !letters.contains(c)).collect();
+        if rest.len() > 20 {
+            return Err(String::from("Alphabet does not fit in the checkerboard!"));
+        }
+
+        let mut rows = vec![top.iter().map(|&c| (c != ' ').then_some(c)).collect()];
+        for chunk in [&rest[..rest.len().min(10)], &rest[rest.len().min(10)..]] {
+            let mut row: Vec<Option<char>> = chunk.iter().map(|&c| Some(c)).collect();
+            row.resize(10, None);
+            rows.push(row);
+        }
+        Ok(Self {
+            alphabet: alphabet.to_string(),
+            header,
+            rows,
+        })
+    }
+
+    fn prefixes(&self) -> Vec<u32> {
+        (0..10)
+            .filter(|&col| self.rows[0][col].is_none())
+            .map(|col| self.header[col])
+            .collect()
+    }
+    fn column(&self, digit: u32) -> usize {
+        self.header.iter().position(|&d| d == digit).unwrap()
+    }
+}
+impl Cipher for Checkerboard {
+    fn encrypt(&self, plaintext: &str) -> String {
+        let prefixes = self.prefixes();
+        let mut output = Vec::new();
+        for c in filter(plaintext, &self.alphabet).chars() {
+            for (r, row) in self.rows.iter().enumerate() {
+                if let Some(col) = row.iter().position(|&x| x == Some(c)) {
+                    if r > 0 {
+                        output.push(prefixes[r - 1]);
+                    }
+                    output.push(self.header[col]);
+                    break;
+                }
+            }
+        }
+        digit_str(&output)
+    }
+    fn decrypt(&self, ciphertxt: &str) -> String {
+        let prefixes = self.prefixes();
+        let mut output = String::new();
+        let mut stream = digits(ciphertxt).into_iter();
+        while let Some(d) = stream.next() {
+            let cell = match prefixes.iter().position(|&p| p == d) {
+                Some(r) => match stream.next() {
+                    Some(next) => self.rows[r + 1][self.column(next)],
+                    None => None,
+                },
+                None => self.rows[0][self.column(d)],
+            };
+            output.extend(cell);
+        }
+        output
+    }
+}
+
+// disrupted: the triangles under successive key columns are filled last
+fn route(n: usize, key: &[u32], disrupted: bool) -> Vec<usize> {
+    let width = key.len();
+    let order = key_order(&digit_str(key), DIGITS);
+    let n_rows = n.div_ceil(width);
+
+    let mut triangle = vec![false; n];
+    if disrupted {
+        let (mut row, mut k) = (0, 0);
+        while row < n_rows {
+            let start = order[k % width];
+            for i in 0..(width - start).min(n_rows - row) {
+                for col in start + i..width {
+                    if let Some(cell) = triangle.get_mut((row + i) * width + col) {
+                        *cell = true;
+                    }
+                }
+            }
+            // triangles are separated by one untouched row
+            row += width - start + 1;
+            k += 1;
+        }
+    }
+    let fill: Vec<usize> = (0..n)
+        .filter(|&p| !triangle[p])
+        .chain((0..n).filter(|&p| triangle[p]))
+        .collect();
+    let mut fill_index = vec![0; n];
+    for (k, &p) in fill.iter().enumerate() {
+        fill_index[p] = k;
+    }
+    order
+        .iter()
+        .flat_map(|&col| (col..n).step_by(width))
+        .map(|p| fill_index[p])
+        .collect()
+}
+
+fn transpose(input: &[u32], key: &[u32], disrupted: bool, decrypt: bool) -> Vec<u32> {
+    let route = route(input.len(), key, disrupted);
+    let mut output = vec![0; input.len()];
+    for (j, &k) in route.iter().enumerate() {
+        match decrypt {
+            false => output[j] = input[k],
+            true => output[k] = input[j],
+        }
+    }
+    output
+}
+
+// `block` is lines K to P
+#[derive(Debug, PartialEq)]
+pub struct Lines {
+    pub c: Vec<u32>,
+    pub g: Vec<u32>,
+    pub h: Vec<u32>,
+    pub j: Vec<u32>,
+    pub block: Vec<u32>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Keys {
+    pub first: Vec<u32>,
+    pub second: Vec<u32>,
+    pub header: Vec<u32>,
+}
+
+#[derive(Debug)]
+pub struct Vic {
+    phrase: String,
+    date: Vec<u32>,
+    personal: usize,
+    indicator: Vec<u32>,
+}
+impl Vic {
+    pub fn new(
+        phrase: &str,
+        date: &str,
+        personal: usize,
+        indicator: Option<&str>,
+    ) -> Result<Self, String> {
+        let phrase = filter(phrase, ENGLISH);
+        if phrase.len() < 20 {
+            return Err(String::from("Phrase must have at least 20 letters!"));
+        }
+        let date = digits(date);
+        if date.len() != 6 {
+            return Err(String::from("Date must have 6 digits!"));
+        }
+        if personal == 0 || personal > 16 {
+            return Err(String::from("Personal number must be in 1..=16!"));
+        }
+        let indicator = match indicator {
+            Some(indicator) => digits(indicator),
+            None => {
+                let mut rng = rand::thread_rng();
+                (0..5).map(|_| rng.gen_range(0..10)).collect()
+            }
+        };
+        if indicator.len() != 5 {
+            return Err(String::from("Indicator must have 5 digits!"));
+        }
+        Ok(Self {
+            phrase: phrase[..20].to_string(),
+            date,
+            personal,
+            indicator,
+        })
+    }
+
+    pub fn lines(&self, indicator: &[u32]) -> Lines {
+        // indicator less the date, chain-extended and added to the first half of the phrase
+        let c: Vec<u32> = (0..5)
+            .map(|i| (indicator[i] + 10 - self.date[i]) % 10)
+            .collect();
+        let e1 = key_digits(&self.phrase[..10], ENGLISH);
+        let e2 = key_digits(&self.phrase[10..], ENGLISH);
+        let g: Vec<u32> = chain_digits(&c, 10)
+            .iter()
+            .zip(e1.iter())
+            .map(|(f, e)| (f + e) % 10)
+            .collect();
+        // encode through the second half of the phrase, read against 1234567890
+        let h: Vec<u32> = g.iter().map(|&d| e2[(d as usize + 9) % 10]).collect();
+        Lines {
+            j: key_digits(&digit_str(&h), DIGITS),
+            block: chain_digits(&h, 60)[10..].to_vec(),
+            c,
+            g,
+            h,
+        }
+    }
+    pub fn keys(&self, indicator: &[u32]) -> Keys {
+        let lines = self.lines(indicator);
+        let j = key_order(&digit_str(&lines.h), DIGITS);
+        let (block, last) = (&lines.block, &lines.block[40..]);
+
+        let a = last[9];
+        let b = *last.iter().rev().find(|&&d| d != a).unwrap_or(&a);
+        let (len1, len2) = (self.personal + b as usize, self.personal + a as usize);
+        let columns: Vec<u32> = j
+            .iter()
+            .flat_map(|&col| (col..50).step_by(10).map(|p| block[p]))
+            .collect();
+        Keys {
+            first: columns[..len1].to_vec(),
+            second: columns[len1..len1 + len2].to_vec(),
+            header: key_digits(&digit_str(last), DIGITS),
+        }
+    }
+
+    fn checkerboard(&self, keys: &Keys) -> Checkerboard {
+        Checkerboard::new(ENGLISH, TOP_ROW, &digit_str(&keys.header)).unwrap()
+    }
+    // as many groups from the end as the last digit of the date
+    fn indicator_at(&self, len: usize) -> usize {
+        len.saturating_sub(5 * self.date[5] as usize)
+    }
+}
+impl Cipher for Vic {
+    fn encrypt(&self, plaintext: &str) -> String {
+        let keys = self.keys(&self.indicator);
+        let encoded = digits(&self.checkerboard(&keys).encrypt(plaintext));
+        let first = transpose(&encoded, &keys.first, false, false);
+        let mut second = transpose(&first, &keys.second, true, false);
+        let at = self.indicator_at(second.len());
+        second.splice(at..at, self.indicator.iter().copied());
+        let groups: Vec<String> = second.chunks(5).map(digit_str).collect();
+        groups.join(" ")
+    }
+    fn decrypt(&self, ciphertxt: &str) -> String {
+        let mut input = digits(ciphertxt);
+        if input.len() < 5 {
+            return String::new();
+        }
+        let at = self.indicator_at(input.len() - 5);
+        let indicator: Vec<u32> = input.drain(at..at + 5).collect();
+        let keys = self.keys(&indicator);
+        let second = transpose(&input, &keys.second, true, true);
+        let first = transpose(&second, &keys.first, false, true);
+        self.checkerboard(&keys).decrypt(&digit_str(&first))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PHRASE: &str = "I REMEMBER A WONDERFUL MOMENT";
+
+    #[test]
+    fn checkerboard() {
+        let alphabet = "ABCDEFGHIJKLMNOPQ/RSTUVWXYZ.";
+        let checkerboard = Checkerboard::new(alphabet, "ET AON RIS", "0123456789").unwrap();
+        assert_eq!(checkerboard.encrypt("ATTACK AT DAWN"), "3113212731223655");
+        assert_eq!(checkerboard.decrypt("3113212731223655"), "ATTACKATDAWN");
+        assert!(Checkerboard::new(ENGLISH, "ETAONRIS", "0123456789").is_err());
+        assert!(Checkerboard::new(ENGLISH, TOP_ROW, "0123456788").is_err());
+    }
+
+    #[test]
+    fn disrupted() {
+        let input: Vec<u32> = (0..20).map(|i| i % 10).collect();
+        let key = [3, 1, 4, 2, 5];
+        let plain = transpose(&input, &key, false, false);
+        assert_eq!(plain[..4], [1, 6, 1, 6]);
+        let disrupted = transpose(&input, &key, true, false);
+        assert_ne!(disrupted, plain);
+        assert_eq!(transpose(&disrupted, &key, true, true), input);
+    }
+
+    #[test]
+    fn keys() {
+        // Hayhanen's date, personal number and indicator; his phrase was Russian, so the
+        // worksheet below was worked out separately for an English one
+        let vic = Vic::new(PHRASE, "030945", 13, Some("20818")).unwrap();
+        let lines = vic.lines(&[2, 0, 8, 1, 8]);
+        let line = |s: &str| digits(s);
+        assert_eq!(lines.c, line("27824"));
+        assert_eq!(lines.g, line("8619877564"));
+        assert_eq!(lines.h, line("9804933281"));
+        assert_eq!(lines.j, line("8605934271"));
+        assert_eq!(lines.block[..10], line("7843265098"));
+        assert_eq!(lines.block[40..], line("7677506663"));
+
+        let keys = vic.keys(&[2, 0, 8, 1, 8]);
+        assert_eq!(keys.first, line("8306309666616005540"));
+        assert_eq!(keys.second, line("6353278291697006"));
+        assert_eq!(keys.header, line("7389204561"));
+        assert_ne!(keys, vic.keys(&[2, 0, 8, 1, 9]));
+    }
+
+    #[test]
+    fn vic() {
+        let plaintext = "WE CONGRATULATE YOU ON A SAFE ARRIVAL";
+        let vic = Vic::new(PHRASE, "030945", 13, Some("20818")).unwrap();
+        let ciphertxt = vic.encrypt(plaintext);
+        let clean = digits(&ciphertxt);
+        let at = clean.len() - 5 - 25;
+        assert_eq!(clean[at..at + 5], [2, 0, 8, 1, 8]);
+
+        let receiver = Vic::new(PHRASE, "030945", 13, None).unwrap();
+        assert_eq!(receiver.decrypt(&ciphertxt), filter(plaintext, ENGLISH));
+        assert!(Vic::new("TOO SHORT", "030945", 13, None).is_err());
+    }
+}
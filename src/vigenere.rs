@@ -66,7 +66,7 @@ mod tests {
     fn kryptos_k1() {
         let plaintext = K1_PLAIN.replace("\n", "").replace(" ", "");
         let ciphertxt = include_str!("kryptos/k1_cipher.txt").replace("\n", "");
-        let vigenere = Vigenere::new(&KRYPTOS, "PALIMPSEST").unwrap();
+        let vigenere = Vigenere::new(KRYPTOS, "PALIMPSEST").unwrap();
         assert_eq!(vigenere.encrypt(&plaintext), ciphertxt);
         assert_eq!(vigenere.decrypt(&ciphertxt), plaintext);
     }
@@ -75,7 +75,7 @@ mod tests {
     fn kryptos_k2() {
         let plaintext = K2_PLAIN.replace("\n", "").replace(" ", "");
         let ciphertxt = include_str!("kryptos/k2_cipher.txt").replace("\n", "");
-        let vigenere = Vigenere::new(&KRYPTOS, "ABSCISSA").unwrap();
+        let vigenere = Vigenere::new(KRYPTOS, "ABSCISSA").unwrap();
         assert_eq!(vigenere.encrypt(&plaintext), ciphertxt);
         assert_eq!(vigenere.decrypt(&ciphertxt), plaintext);
     }
@@ -83,7 +83,7 @@ mod tests {
     #[test]
     fn variant() {
         let ciphertxt = "BV LXL XZWKCEN KGJBZAYBV BZJZ VBXI WZZZAYBV ?";
-        let vigenere = Vigenere::new(&KRYPTOS, "ABSCISSA").unwrap();
+        let vigenere = Vigenere::new(KRYPTOS, "ABSCISSA").unwrap();
         assert_eq!(vigenere.decrypt(K2_PLAIN)[..ciphertxt.len()], *ciphertxt);
         assert_eq!(vigenere.encrypt(ciphertxt), K2_PLAIN[..ciphertxt.len()]);
     }
@@ -92,7 +92,7 @@ mod tests {
     fn beaufort() {
         let plaintext = "IT WAS GIOVANNI VESTRI";
         let ciphertxt = "XYENKCKRWAAPAXBZHWU";
-        let beaufort = Vigenere::new_beaufort(&ENGLISH, "FRANCIS").unwrap();
+        let beaufort = Vigenere::new_beaufort(ENGLISH, "FRANCIS").unwrap();
         assert_eq!(beaufort.encrypt(plaintext).replace(" ", ""), ciphertxt);
         assert_eq!(beaufort.decrypt(ciphertxt), plaintext.replace(" ", ""));
     }